@@ -13,7 +13,10 @@ use pbc_contract_common::address::Shortname;
 use pbc_contract_common::address::ShortnameCallback;
 use pbc_contract_common::context::{CallbackContext, ContractContext};
 use pbc_contract_common::events::EventGroup;
-use pbc_contract_common::shortname::{ShortnameZkComputation, ShortnameZkComputeComplete};
+use pbc_contract_common::shortname::{
+    ShortnameZkComputation, ShortnameZkComputeComplete, ShortnameZkVariableInputted,
+};
+use pbc_contract_common::sorted_vec_map::{SortedVecMap, SortedVecSet};
 use pbc_contract_common::zk::{CalculationStatus, SecretVarId, ZkInputDef, ZkState, ZkStateChange};
 use pbc_zk::Sbu32;
 use read_write_rpc_derive::ReadWriteRPC;
@@ -24,15 +27,29 @@ use read_write_state_derive::ReadWriteState;
 #[repr(u8)]
 enum SecretVarType {
     #[discriminant(0)]
-    Contribution { owner: Address, timestamp: i64 },
+    Contribution {
+        owner: Address,
+        timestamp: i64,
+        transfer_amount: u32, // Charged once inputted, 0 when paid with contribute_tokens
+    },
     #[discriminant(1)]
     TokenBalance { owner: Address, timestamp: i64 },
     #[discriminant(2)]
     ThresholdCheckResult { _placeholder: u8 },
-    #[discriminant(3)]
-    ConditionalTotal { _placeholder: u8 },
     #[discriminant(4)]
     ActualTotal { _placeholder: u8 },
+    #[discriminant(5)]
+    ContributorCount { _placeholder: u8 },
+    #[discriminant(6)]
+    MedianContribution { _placeholder: u8 },
+    #[discriminant(7)]
+    ShareMultipleCheck { share_price: u32 },
+    #[discriminant(8)]
+    LeaderboardEntry { rank: u8 },
+    #[discriminant(9)]
+    LimitCheck { per_person_limit: u32 },
+    #[discriminant(10)]
+    AverageContribution { _placeholder: u8 },
 }
 
 /// Campaign status
@@ -45,193 +62,647 @@ enum CampaignStatus {
     Computing {},
     #[discriminant(2)]
     Completed {},
+    #[discriminant(3)]
+    Setup {},
+}
+
+/// Role of the caller, for gating UI controls
+#[derive(ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+#[repr(u8)]
+enum Role {
+    #[discriminant(0)]
+    None {},
+    #[discriminant(1)]
+    Owner {},
+    #[discriminant(2)]
+    Contributor {},
+}
+
+/// Proof of inclusion in a campaign - A verifier checks that the campaign's ZK state holds a
+/// Contribution variable with this owner and timestamp; the amount is never part of it
+#[derive(ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+struct ContributionAttestation {
+    campaign_address: Address,
+    contributor: Address,
+    timestamp: i64,
+}
+
+/// ZK layer diagnostics - No variable data is included
+#[derive(ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+struct CalculationDiagnostics {
+    calculation_state: u8, // 0 Waiting, 1 Calculating, 2 Output, 3 MaliciousBehaviour
+    secret_variable_count: u32,
+}
+
+/// Flattened campaign state for explorers - Hidden or absent values use the field type's maximum
+/// (u64::MAX for total_raised, u32::MAX for num_contributors, i64::MAX for completed_at)
+#[derive(ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+struct PublicCampaignView {
+    owner: Address,
+    title: String,
+    token_address: Address,
+    token_decimals: u8,
+    funding_target: u32,
+    status: u8, // CampaignStatus discriminant
+    is_successful: bool,
+    total_raised: u64,
+    num_contributors: u32,
+    withdrawal_status: u8, // WithdrawalStatus discriminant
+    completed_at: i64,
+}
+
+/// Initialize settings besides the title, token and target - Zero or None disables each option
+#[derive(ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+struct CampaignSettings {
+    token_decimals: u8,
+    max_contributors: u32, // 0 selects DEFAULT_MAX_CONTRIBUTORS
+    factory_address: Option<Address>,
+    attestation_contract: Option<Address>,
+    reveal_delay_millis: i64,
+    contribution_fee: u32,
+    contribution_deadline: Option<i64>,
+    contribution_cooldown_millis: i64,
+}
+
+/// Withdrawal progress - Revealing and transferring happen in separate transactions
+#[derive(ReadWriteState, ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+#[repr(u8)]
+enum WithdrawalStatus {
+    #[discriminant(0)]
+    NotStarted {},
+    #[discriminant(1)]
+    Revealing {},
+    #[discriminant(2)]
+    Transferring {},
+    #[discriminant(3)]
+    Withdrawn {},
+}
+
+/// Lifecycle reveal awaiting its opened variables - Recorded when the variables are opened
+#[derive(ReadWriteState, ReadWriteRPC, Debug, Clone, PartialEq, CreateTypeSpec)]
+#[repr(u8)]
+enum RevealPhase {
+    #[discriminant(0)]
+    None {},
+    #[discriminant(1)]
+    Threshold {},
+    #[discriminant(3)]
+    Withdrawal {},
 }
 
 /// Contract state with separate trackers for public display vs private withdrawal
+///
+/// `state_version` is serialized first so tooling can detect state written by an older layout.
+/// PBC does not migrate state on upgrade: bump STATE_VERSION whenever fields change and reject
+/// or convert state carrying a different version.
 #[state]
 struct ContractState {
+    state_version: u8,
     owner: Address,
     title: String,
     description: String,
     token_address: Address,
+    token_decimals: u8,
     funding_target: u32,
+    max_contributors: u32,
     status: CampaignStatus,
-    total_raised: Option<u32>, // Public display (only if threshold met)
+    total_raised: Option<u32>, // The funding target once a success is public, never the exact sum
+    total_raised_base: Option<u128>, // total_raised in the token's base units, so also capped
     num_contributors: Option<u32>,
     is_successful: bool,
-    funds_withdrawn: bool,
-    balance_tracker_id: Option<SecretVarId>, // For public display (conditional)
+    withdrawal_status: WithdrawalStatus,
     withdrawal_tracker_id: Option<SecretVarId>, // For owner withdrawal (actual total)
+    factory_address: Option<Address>, // Notified on completion, if the campaign was created by one
+    attestation_contract: Option<Address>, // Unique-human check required before contributing
+    attested_contributors: SortedVecSet<Address>,
+    confirmed_contributions: SortedVecMap<Address, u32>, // Token units actually transferred in
+    max_possible_total: u32, // Public sum of confirmed contributions, an upper bound on the total
+    computation_started_at: Option<i64>,
+    median_contribution: Option<u32>, // Analytics, revealed on request after completion
+    share_price: Option<u32>,
+    all_contributions_whole_shares: Option<bool>, // Aggregate only, never per contribution
+    matcher: Option<Address>, // Sponsor matching the funding target up to match_cap
+    match_cap: u32,
+    matched_amount: Option<u32>, // Settled once the sponsor's transfer completes or fails
+    reveal_delay_millis: i64,    // Withholds the outcome after the computation finishes
+    completed_at: Option<i64>,   // Set in every completion path, successful or not
+    reveal_phase: RevealPhase,
+    pending_owner: Option<Address>, // Proposed owner until they accept
+    contribution_fee: u32,          // Charged on top of each token transfer and paid to the owner
+    top_contributors: Vec<Address>, // Owners of the largest contributions, largest first
+    per_person_limit: Option<u32>,
+    all_within_limit: Option<bool>, // Aggregate only, never per contribution
+    on_success_contract: Option<Address>, // Called once the total of a successful campaign is public
+    on_success_shortname: Option<u32>,
+    contribution_deadline: Option<i64>, // No contributions after this time, ending stays open
+    average_contribution: Option<u32>,  // Analytics, revealed on request after completion
+    contribution_cooldown_millis: i64,  // Minimum time between contributions sent by one address
+    contributions_paused: bool,
+    paid_contributions: SortedVecSet<SecretVarId>, // Contribution variables with a confirmed transfer
+    pending_withdrawals: SortedVecSet<Address>,    // Contributors whose variables are being opened
+    result_computed_at: Option<i64>, // Start of the reveal delay, set when the computation finishes
+    unclaimed_fees: u32, // Fees whose forward to the owner failed, paid out by claim_fees
 }
 
 /// Constants
+const STATE_VERSION: u8 = 25;
 const TOKEN_TRANSFER_SHORTNAME: u8 = 0x01;
 const CONTRIBUTION_CALLBACK_SHORTNAME: u32 = 0x31;
+const CONTRIBUTION_INPUTTED_SHORTNAME: u32 = 0x45;
+const WITHDRAWAL_CALLBACK_SHORTNAME: u32 = 0x32;
+const MATCHING_CALLBACK_SHORTNAME: u32 = 0x34;
+const REFUND_CALLBACK_SHORTNAME: u32 = 0x35;
+const FEE_CALLBACK_SHORTNAME: u32 = 0x36;
 const THRESHOLD_CHECK_COMPLETE_SHORTNAME: u32 = 0x42;
 const ZK_THRESHOLD_CHECK_SHORTNAME: u32 = 0x61;
-const WEI_PER_TOKEN_UNIT: u128 = 1_000_000_000_000;
+const ANALYTICS_COMPLETE_SHORTNAME: u32 = 0x43;
+const ZK_MEDIAN_CONTRIBUTION_SHORTNAME: u32 = 0x62;
+const ZK_SHARE_MULTIPLE_CHECK_SHORTNAME: u32 = 0x63;
+const ZK_TOP_CONTRIBUTIONS_SHORTNAME: u32 = 0x64;
+const ZK_LIMIT_CHECK_SHORTNAME: u32 = 0x65;
+const ZK_AVERAGE_CONTRIBUTION_SHORTNAME: u32 = 0x66;
+/// Number of leaderboard entries produced by the top contributions computation
+const LEADERBOARD_SIZE: u8 = 3;
+const FACTORY_UPDATE_CAMPAIGN_STATUS_SHORTNAME: u32 = 0x05;
+const ATTESTATION_CALLBACK_SHORTNAME: u32 = 0x33;
+/// Attestation contract interface: an action taking the contributor address that fails
+/// unless the address holds a unique-human attestation
+const ATTESTATION_CHECK_SHORTNAME: u32 = 0x01;
+/// Contribution amounts are counted in token units of 10^-6 tokens
+const TOKEN_UNIT_DECIMALS: u8 = 6;
+const MAX_TOKEN_DECIMALS: u8 = 30;
+/// Bounds the cost of summing contributions in ZK
+const DEFAULT_MAX_CONTRIBUTORS: u32 = 500;
+/// How long the threshold computation may run before the owner can abort it
+const COMPUTATION_TIMEOUT_MILLIS: i64 = 7 * 24 * 60 * 60 * 1000;
 
-fn token_units_to_wei(token_units: u32) -> u128 {
-    (token_units as u128) * WEI_PER_TOKEN_UNIT
+/// Amount in token units - The unit of contribution amounts and of the ZK sums
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct TokenUnits(u32);
+
+/// Amount in the token's smallest unit - The unit of MPC-20 transfers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct BaseUnits(u128);
+
+impl TokenUnits {
+    /// Panics instead of wrapping; within MAX_TOKEN_DECIMALS any u32 amount fits
+    fn to_base_units(self, token_decimals: u8) -> BaseUnits {
+        let base_per_token_unit = token_decimals
+            .checked_sub(TOKEN_UNIT_DECIMALS)
+            .and_then(|exponent| 10u128.checked_pow(exponent as u32))
+            .expect("Token decimals out of range for base unit conversion");
+        let base_amount = (self.0 as u128)
+            .checked_mul(base_per_token_unit)
+            .expect("Amount overflows when converted to base units");
+        BaseUnits(base_amount)
+    }
 }
 
-/// Initialize contract
-#[init(zk = true)]
-fn initialize(
-    ctx: ContractContext,
-    _zk_state: ZkState<SecretVarType>,
-    title: String,
-    description: String,
-    token_address: Address,
-    funding_target: u32,
-) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    assert!(!title.is_empty(), "Title cannot be empty");
-    assert!(!description.is_empty(), "Description cannot be empty");
-    assert!(funding_target > 0, "Funding target must be greater than 0");
+/// Preflight for a contribution transfer - The callback adds the amount to the public totals after
+/// the tokens have moved, so an overflow there would strand the transfer
+fn assert_contribution_fits(state: &ContractState, amount: TokenUnits) {
+    assert!(amount.0 > 0, "Contribution amount must be greater than 0");
+    assert!(
+        state.max_possible_total.checked_add(amount.0).is_some(),
+        "Contribution would overflow the campaign total"
+    );
+}
 
-    let state = ContractState {
-        owner: ctx.sender,
-        title,
-        description,
-        token_address,
-        funding_target,
-        status: CampaignStatus::Active {},
-        total_raised: None,
-        num_contributors: None,
-        is_successful: false,
-        funds_withdrawn: false,
-        balance_tracker_id: None,
-        withdrawal_tracker_id: None,
-    };
+/// Transfer of a contribution from the payer to the campaign, verified by the contribution callback
+/// The contribution is credited to `contributor`, which differs from the payer for gifts, and the
+/// callback marks `variable_id` as paid. The payer also pays the contribution fee, which is not
+/// credited.
+fn contribution_transfer(
+    context: &ContractContext,
+    state: &ContractState,
+    payer: Address,
+    contributor: Address,
+    variable_id: SecretVarId,
+    amount: TokenUnits,
+) -> EventGroup {
+    assert_contribution_fits(state, amount);
 
-    (state, vec![], vec![])
+    let charged_amount = amount
+        .0
+        .checked_add(state.contribution_fee)
+        .expect("Contribution amount plus fee overflows");
+    let base_amount = TokenUnits(charged_amount).to_base_units(state.token_decimals);
+
+    let mut event_group = EventGroup::builder();
+
+    event_group
+        .call(state.token_address, Shortname::from_u32(0x03))
+        .argument(payer)
+        .argument(context.contract_address)
+        .argument(base_amount.0)
+        .done();
+
+    event_group
+        .with_callback(ShortnameCallback::from_u32(CONTRIBUTION_CALLBACK_SHORTNAME))
+        .argument(variable_id.raw_id)
+        .argument(amount.0)
+        .argument(contributor)
+        .done();
+
+    event_group.build()
 }
 
-/// Add contribution
-#[zk_on_secret_input(shortname = 0x40)]
-fn add_contribution(
-    context: ContractContext,
-    state: ContractState,
-    _zk_state: ZkState<SecretVarType>,
-) -> (
-    ContractState,
-    Vec<EventGroup>,
-    ZkInputDef<SecretVarType, Sbu32>,
+/// Number of contribution variables, i.e. contributions made so far
+fn count_contributions(zk_state: &ZkState<SecretVarType>) -> u32 {
+    zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, var)| matches!(var.metadata, SecretVarType::Contribution { .. }))
+        .count() as u32
+}
+
+/// Contribution variable with the address that submitted it and the address it is credited to
+struct ContributionVariable {
+    variable_id: SecretVarId,
+    submitter: Address,
+    owner: Address,
+}
+
+/// Contribution variables in the ZK state
+fn contribution_variables(zk_state: &ZkState<SecretVarType>) -> Vec<ContributionVariable> {
+    zk_state
+        .secret_variables
+        .iter()
+        .filter_map(|(variable_id, var)| match &var.metadata {
+            SecretVarType::Contribution { owner, .. } => Some(ContributionVariable {
+                variable_id: *variable_id,
+                submitter: var.owner,
+                owner: *owner,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Contribution variables without a confirmed transfer, even when their owner paid for others
+fn unpaid_contribution_ids(
+    state: &ContractState,
+    contributions: &[ContributionVariable],
+) -> Vec<SecretVarId> {
+    contributions
+        .iter()
+        .map(|contribution| contribution.variable_id)
+        .filter(|variable_id| !state.paid_contributions.contains(variable_id))
+        .collect()
+}
+
+/// An address may take part in one unpaid commitment at a time, as submitter or owner, which
+/// bounds free add_contribution spam. Earlier paid contributions do not lift the limit.
+fn assert_no_unpaid_commitment(
+    state: &ContractState,
+    contributions: &[ContributionVariable],
+    address: Address,
 ) {
-    assert_eq!(
-        state.status,
-        CampaignStatus::Active {},
-        "Contributions can only be made when campaign is active"
+    let has_commitment = contributions.iter().any(|contribution| {
+        !state.paid_contributions.contains(&contribution.variable_id)
+            && (contribution.submitter == address || contribution.owner == address)
+    });
+    assert!(
+        !has_commitment,
+        "Previous contribution must be paid before committing another"
     );
+}
 
-    let metadata = SecretVarType::Contribution {
-        owner: context.sender,
-        timestamp: context.block_production_time,
-    };
+/// Only paid contributions take a slot, so unpaid commitments cannot lock out real contributors
+fn assert_contribution_slot_available(state: &ContractState) {
+    assert!(
+        (state.paid_contributions.len() as u32) < state.max_contributors,
+        "Campaign has reached its maximum number of contributions"
+    );
+}
 
-    let input_def = ZkInputDef::with_metadata(None, metadata);
-    (state, vec![], input_def)
+/// Refund contributions opened by withdraw_contribution and delete their variables
+/// Refunds are capped by the tokens the contributor actually transferred in, and deletion happens
+/// in the same step that queues the refund so a contribution is never refunded twice. Unpaid
+/// variables are deleted without a refund, and variables that are already gone are skipped.
+fn refund_opened_contributions(
+    mut state: ContractState,
+    zk_state: &ZkState<SecretVarType>,
+    opened_variables: Vec<SecretVarId>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let mut event_groups = vec![];
+    let mut withdrawn_variables = vec![];
+
+    for variable_id in opened_variables {
+        let Some(variable) = zk_state.get_variable(variable_id) else {
+            continue;
+        };
+        let SecretVarType::Contribution { owner, .. } = &variable.metadata else {
+            continue;
+        };
+        let contributor = *owner;
+        state.pending_withdrawals.remove(&contributor);
+        withdrawn_variables.push(variable_id);
+        if !state.paid_contributions.remove(&variable_id) {
+            continue;
+        }
+
+        let secret_amount = opened_u32(&variable.data).unwrap_or(0);
+        let confirmed = state
+            .confirmed_contributions
+            .get(&contributor)
+            .copied()
+            .unwrap_or(0);
+        let refund = secret_amount.min(confirmed);
+
+        if refund > 0 {
+            event_groups.push(refund_transfer(&mut state, contributor, TokenUnits(refund)));
+        }
+    }
+
+    (
+        state,
+        event_groups,
+        vec![ZkStateChange::DeleteVariables {
+            variables: withdrawn_variables,
+        }],
+    )
 }
 
-/// Token transfer
-#[action(shortname = 0x07, zk = true)]
-fn contribute_tokens(
-    context: ContractContext,
-    state: ContractState,
-    zk_state: ZkState<SecretVarType>,
-    amount: u32,
-) -> (ContractState, Vec<EventGroup>) {
-    assert_eq!(
-        state.status,
-        CampaignStatus::Active {},
-        "Contributions can only be made when campaign is active"
-    );
+/// Credit a paid contribution to the contributor's confirmed amount and the public total
+fn credit_contribution(state: &mut ContractState, contributor: Address, amount: TokenUnits) {
+    let confirmed = state
+        .confirmed_contributions
+        .get(&contributor)
+        .copied()
+        .unwrap_or(0);
+    let new_confirmed = confirmed
+        .checked_add(amount.0)
+        .expect("Confirmed contribution overflows");
+    state.max_possible_total = state
+        .max_possible_total
+        .checked_add(amount.0)
+        .expect("Contribution would overflow the campaign total");
+    state
+        .confirmed_contributions
+        .insert(contributor, new_confirmed);
+}
 
-    assert!(amount > 0, "Contribution amount must be greater than 0");
+/// Record a confirmed contribution transfer - The variable is marked paid unless it was deleted
+/// while the transfer was pending; the tokens are credited either way so they stay refundable
+fn confirm_contribution(
+    state: &mut ContractState,
+    variable_id: Option<SecretVarId>,
+    contributor: Address,
+    amount: TokenUnits,
+) {
+    credit_contribution(state, contributor, amount);
+    if let Some(variable_id) = variable_id {
+        state.paid_contributions.insert(variable_id);
+    }
+}
 
-    let user_contribution_count = zk_state.secret_variables.iter()
-        .filter(|(_, var)| matches!(&var.metadata, SecretVarType::Contribution { owner, .. } if *owner == context.sender))
-        .count();
+/// Settle a contribution transfer reported by the contribution callback
+/// A failed transfer moved no tokens, so nothing is credited and the unpaid variable is deleted;
+/// it would otherwise block its submitter and owner from committing again
+fn settle_contribution_transfer(
+    state: &mut ContractState,
+    succeeded: bool,
+    variable_id: Option<SecretVarId>,
+    contributor: Address,
+    amount: TokenUnits,
+) -> Vec<ZkStateChange> {
+    if succeeded {
+        confirm_contribution(state, variable_id, contributor, amount);
+        return vec![];
+    }
+    match variable_id {
+        Some(variable_id) if !state.paid_contributions.contains(&variable_id) => {
+            vec![ZkStateChange::DeleteVariables {
+                variables: vec![variable_id],
+            }]
+        }
+        _ => vec![],
+    }
+}
+
+/// Transfer of contribution fees to the owner, verified by the fee callback
+fn fee_transfer(state: &ContractState, fee: TokenUnits) -> EventGroup {
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(state.token_address, Shortname::from_u32(0x01))
+        .argument(state.owner)
+        .argument(fee.to_base_units(state.token_decimals).0)
+        .done();
 
+    event_group
+        .with_callback(ShortnameCallback::from_u32(FEE_CALLBACK_SHORTNAME))
+        .argument(fee.0)
+        .done();
+
+    event_group.build()
+}
+
+/// Settle a fee transfer - A failed transfer left the fee in the contract, so it is owed to the
+/// owner until claim_fees pays it out
+fn settle_fee_transfer(state: &mut ContractState, succeeded: bool, fee: TokenUnits) {
+    if !succeeded {
+        state.unclaimed_fees = state
+            .unclaimed_fees
+            .checked_add(fee.0)
+            .expect("Unclaimed fees overflow");
+    }
+}
+
+/// Debit a refund from the contributor's confirmed amount and transfer it back
+/// A failed transfer is credited again by the refund callback
+fn refund_transfer(
+    state: &mut ContractState,
+    contributor: Address,
+    refund: TokenUnits,
+) -> EventGroup {
+    let confirmed = state
+        .confirmed_contributions
+        .get(&contributor)
+        .copied()
+        .unwrap_or(0);
+    assert!(refund.0 > 0, "No contribution to refund");
     assert!(
-        user_contribution_count > 0,
-        "Must create contribution commitment first"
+        refund.0 <= confirmed && refund.0 <= state.max_possible_total,
+        "Refund exceeds the confirmed contribution"
     );
-
-    let wei_amount = token_units_to_wei(amount);
+    state.max_possible_total -= refund.0;
+    if confirmed - refund.0 == 0 {
+        state.confirmed_contributions.remove(&contributor);
+    } else {
+        state
+            .confirmed_contributions
+            .insert(contributor, confirmed - refund.0);
+    }
 
     let mut event_group = EventGroup::builder();
-
     event_group
-        .call(state.token_address, Shortname::from_u32(0x03))
-        .argument(context.sender)
-        .argument(context.contract_address)
-        .argument(wei_amount)
+        .call(state.token_address, Shortname::from_u32(0x01))
+        .argument(contributor)
+        .argument(refund.to_base_units(state.token_decimals).0)
         .done();
 
     event_group
-        .with_callback(ShortnameCallback::from_u32(CONTRIBUTION_CALLBACK_SHORTNAME))
-        .argument(amount)
+        .with_callback(ShortnameCallback::from_u32(REFUND_CALLBACK_SHORTNAME))
+        .argument(contributor)
+        .argument(refund.0)
         .done();
 
-    (state, vec![event_group.build()])
+    event_group.build()
 }
 
-/// Callback
-#[callback(shortname = 0x31, zk = true)]
-fn contribute_callback(
-    _ctx: ContractContext,
-    callback_ctx: CallbackContext,
-    state: ContractState,
-    _zk_state: ZkState<SecretVarType>,
-    _amount: u32,
-) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    if !callback_ctx.success {
-        panic!("Token transfer failed");
+/// Notify the factory of the campaign outcome and call the success hook - Either is skipped
+/// when not configured
+/// The success hook receives (total_raised: u32, num_contributors: u32); total_raised is capped at
+/// the funding target, so a successful campaign always reports the target
+fn completion_events(state: &ContractState) -> Vec<EventGroup> {
+    let mut events = vec![];
+
+    if let Some(factory_address) = state.factory_address {
+        let mut event_group = EventGroup::builder();
+        event_group
+            .call(
+                factory_address,
+                Shortname::from_u32(FACTORY_UPDATE_CAMPAIGN_STATUS_SHORTNAME),
+            )
+            .argument(state.is_successful)
+            .argument(state.total_raised)
+            .done();
+        events.push(event_group.build());
     }
-    (state, vec![], vec![])
+
+    if let (true, Some(hook_contract), Some(hook_shortname)) = (
+        state.is_successful,
+        state.on_success_contract,
+        state.on_success_shortname,
+    ) {
+        let mut event_group = EventGroup::builder();
+        event_group
+            .call(hook_contract, Shortname::from_u32(hook_shortname))
+            .argument(state.total_raised.unwrap_or(0))
+            .argument(state.num_contributors.unwrap_or(0))
+            .done();
+        events.push(event_group.build());
+    }
+
+    events
 }
 
-/// End campaign - Now creates 3 ZK variables for privacy-preserving withdrawal
-#[action(shortname = 0x01, zk = true)]
-fn end_campaign(
-    context: ContractContext,
-    mut state: ContractState,
-    zk_state: ZkState<SecretVarType>,
-) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    assert_eq!(
-        context.sender, state.owner,
-        "Only owner can end the campaign"
-    );
+/// Decodes a revealed Sbu32 from opened variable data
+fn opened_u32(data: &Option<Vec<u8>>) -> Option<u32> {
+    data.as_ref()
+        .filter(|data| data.len() >= 4)
+        .map(|data| u32::from_le_bytes(data[0..4].try_into().unwrap_or([0u8; 4])))
+}
+
+/// Checks shared by every secret-input contribution path
+fn assert_accepting_contributions(
+    context: &ContractContext,
+    state: &ContractState,
+    zk_state: &ZkState<SecretVarType>,
+    contributor: Address,
+) {
     assert_eq!(
         state.status,
         CampaignStatus::Active {},
-        "Campaign can only be ended from Active state"
+        "Contributions can only be made when campaign is active"
     );
-    assert_eq!(
-        zk_state.calculation_state,
-        CalculationStatus::Waiting,
-        "Computation must start from Waiting state"
+    assert!(!state.contributions_paused, "Contributions are paused");
+    assert_before_contribution_deadline(context, state);
+    // Keyed on the sender rather than the credited contributor, so gifts to many beneficiaries
+    // are rate limited as well
+    if state.contribution_cooldown_millis > 0 {
+        let last_contribution = zk_state
+            .secret_variables
+            .iter()
+            .filter(|(_, var)| var.owner == context.sender)
+            .filter_map(|(_, var)| match &var.metadata {
+                SecretVarType::Contribution { timestamp, .. } => Some(*timestamp),
+                _ => None,
+            })
+            .max();
+        assert_cooldown_elapsed(context, state, last_contribution);
+    }
+    assert_contribution_slot_available(state);
+    if state.attestation_contract.is_some() {
+        assert!(
+            state.attested_contributors.contains(&contributor),
+            "Contributor must be verified with verify_contributor first"
+        );
+    }
+}
+
+/// Rejects a contribution submitted within the cooldown of the sender's previous one
+fn assert_cooldown_elapsed(
+    context: &ContractContext,
+    state: &ContractState,
+    last_contribution: Option<i64>,
+) {
+    if let Some(last_contribution) = last_contribution {
+        assert!(
+            context.block_production_time
+                >= last_contribution.saturating_add(state.contribution_cooldown_millis),
+            "Contribution cooldown has not elapsed yet"
+        );
+    }
+}
+
+/// Contributions close at the contribution deadline, the owner can end the campaign any time
+fn assert_before_contribution_deadline(context: &ContractContext, state: &ContractState) {
+    if let Some(contribution_deadline) = state.contribution_deadline {
+        assert!(
+            context.block_production_time < contribution_deadline,
+            "Contribution deadline has passed"
+        );
+    }
+}
+
+/// Withdrawable part of the revealed total - Commitments whose transfer never completed are
+/// counted in the secret sum but not held by the contract
+fn confirmed_withdrawal(state: &ContractState, revealed_total: u32) -> u32 {
+    let matched_amount = state.matched_amount.unwrap_or(0);
+    // A sum past u32::MAX is above max_possible_total anyway
+    revealed_total
+        .checked_add(matched_amount)
+        .map_or(state.max_possible_total, |total| {
+            total.min(state.max_possible_total)
+        })
+}
+
+/// Pull the sponsor's match of min(funding_target, match_cap) into the campaign
+/// The exact total stays private, so a success is matched as if it raised exactly the target. The
+/// match is also limited to what max_possible_total can still count
+fn matching_transfer(
+    context: &ContractContext,
+    state: &ContractState,
+    matcher: Address,
+) -> EventGroup {
+    let match_amount = TokenUnits(
+        state
+            .funding_target
+            .min(state.match_cap)
+            .min(u32::MAX - state.max_possible_total),
     );
+    let base_amount = match_amount.to_base_units(state.token_decimals);
 
-    let contributions = zk_state
-        .secret_variables
-        .iter()
-        .filter(|(_, var)| matches!(var.metadata, SecretVarType::Contribution { .. }))
-        .count();
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(state.token_address, Shortname::from_u32(0x03))
+        .argument(matcher)
+        .argument(context.contract_address)
+        .argument(base_amount.0)
+        .done();
 
-    let num_contributors = contributions as u32;
-    state.status = CampaignStatus::Computing {};
-    state.num_contributors = Some(num_contributors);
+    event_group
+        .with_callback(ShortnameCallback::from_u32(MATCHING_CALLBACK_SHORTNAME))
+        .argument(match_amount.0)
+        .done();
 
-    if contributions == 0 {
-        // No contributions, campaign automatically fails
-        state.status = CampaignStatus::Completed {};
-        state.is_successful = false;
-        state.total_raised = None;
-        return (state, vec![], vec![]);
-    }
+    event_group.build()
+}
 
+/// Start the threshold computation over the confirmed contributions
+fn threshold_computation(context: &ContractContext, state: &mut ContractState) -> ZkStateChange {
     let function_shortname = ShortnameZkComputation::from_u32(ZK_THRESHOLD_CHECK_SHORTNAME);
     let on_complete_hook = Some(ShortnameZkComputeComplete::from_u32(
         THRESHOLD_CHECK_COMPLETE_SHORTNAME,
@@ -240,58 +711,493 @@ fn end_campaign(
     // Create 3 output variables for privacy-preserving withdrawal
     let output_metadata = vec![
         SecretVarType::ThresholdCheckResult { _placeholder: 0 }, // Always revealed
-        SecretVarType::ConditionalTotal { _placeholder: 0 }, // Public display (only if successful)
         SecretVarType::ActualTotal { _placeholder: 0 }, // Private withdrawal (always available to owner)
+        SecretVarType::ContributorCount { _placeholder: 0 }, // Always revealed
     ];
 
     let input_arguments = vec![state.funding_target];
+    state.status = CampaignStatus::Computing {};
+    state.computation_started_at = Some(context.block_production_time);
 
-    let computation_change = ZkStateChange::start_computation_with_inputs(
+    ZkStateChange::start_computation_with_inputs(
         function_shortname,
         output_metadata,
         input_arguments,
         on_complete_hook,
+    )
+}
+
+/// Transfer of the withdrawn tokens to the owner, verified by the withdrawal callback
+fn withdrawal_transfer(state: &ContractState, tokens_to_withdraw: TokenUnits) -> EventGroup {
+    assert!(tokens_to_withdraw.0 > 0, "No funds to withdraw");
+    assert!(
+        tokens_to_withdraw.0 <= state.max_possible_total,
+        "Withdrawal exceeds the tokens held by the campaign"
     );
 
-    (state, vec![], vec![computation_change])
-}
+    let withdraw_amount = tokens_to_withdraw.to_base_units(state.token_decimals);
 
-/// Computation complete - Now handles 3 variables
-#[zk_on_compute_complete(shortname = 0x42)]
-fn threshold_check_complete(
-    _context: ContractContext,
-    mut state: ContractState,
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(state.token_address, Shortname::from_u32(0x01))
+        .argument(state.owner)
+        .argument(withdraw_amount.0)
+        .done();
+
+    event_group
+        .with_callback(ShortnameCallback::from_u32(WITHDRAWAL_CALLBACK_SHORTNAME))
+        .argument(tokens_to_withdraw.0)
+        .done();
+
+    event_group.build()
+}
+
+/// Initialize contract
+#[init(zk = true)]
+fn initialize(
+    ctx: ContractContext,
+    _zk_state: ZkState<SecretVarType>,
+    title: String,
+    description: String,
+    token_address: Address,
+    funding_target: u32,
+    settings: CampaignSettings,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let CampaignSettings {
+        token_decimals,
+        max_contributors,
+        factory_address,
+        attestation_contract,
+        reveal_delay_millis,
+        contribution_fee,
+        contribution_deadline,
+        contribution_cooldown_millis,
+    } = settings;
+
+    assert!(!title.is_empty(), "Title cannot be empty");
+    assert!(!description.is_empty(), "Description cannot be empty");
+    assert!(funding_target > 0, "Funding target must be greater than 0");
+    assert!(
+        token_decimals >= TOKEN_UNIT_DECIMALS,
+        "Token decimals must be at least {}",
+        TOKEN_UNIT_DECIMALS
+    );
+    assert!(
+        token_decimals <= MAX_TOKEN_DECIMALS,
+        "Token decimals cannot exceed {}",
+        MAX_TOKEN_DECIMALS
+    );
+    assert!(reveal_delay_millis >= 0, "Reveal delay cannot be negative");
+    assert!(
+        contribution_cooldown_millis >= 0,
+        "Contribution cooldown cannot be negative"
+    );
+    if let Some(contribution_deadline) = contribution_deadline {
+        assert!(
+            contribution_deadline > ctx.block_production_time,
+            "Contribution deadline must be in the future"
+        );
+    }
+
+    // Zero selects the default limit
+    let max_contributors = if max_contributors == 0 {
+        DEFAULT_MAX_CONTRIBUTORS
+    } else {
+        max_contributors
+    };
+
+    let state = ContractState {
+        state_version: STATE_VERSION,
+        owner: ctx.sender,
+        title,
+        description,
+        token_address,
+        token_decimals,
+        funding_target,
+        max_contributors,
+        status: CampaignStatus::Setup {},
+        total_raised: None,
+        total_raised_base: None,
+        num_contributors: None,
+        is_successful: false,
+        withdrawal_status: WithdrawalStatus::NotStarted {},
+        withdrawal_tracker_id: None,
+        factory_address,
+        attestation_contract,
+        attested_contributors: SortedVecSet::new(),
+        confirmed_contributions: SortedVecMap::new(),
+        max_possible_total: 0,
+        computation_started_at: None,
+        median_contribution: None,
+        share_price: None,
+        all_contributions_whole_shares: None,
+        matcher: None,
+        match_cap: 0,
+        matched_amount: None,
+        reveal_delay_millis,
+        completed_at: None,
+        reveal_phase: RevealPhase::None {},
+        pending_owner: None,
+        contribution_fee,
+        top_contributors: vec![],
+        per_person_limit: None,
+        all_within_limit: None,
+        on_success_contract: None,
+        on_success_shortname: None,
+        contribution_deadline,
+        average_contribution: None,
+        contribution_cooldown_millis,
+        contributions_paused: false,
+        paid_contributions: SortedVecSet::new(),
+        pending_withdrawals: SortedVecSet::new(),
+        result_computed_at: None,
+        unclaimed_fees: 0,
+    };
+
+    (state, vec![], vec![])
+}
+
+/// Add contribution
+#[zk_on_secret_input(shortname = 0x40)]
+fn add_contribution(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (
+    ContractState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, Sbu32>,
+) {
+    assert_accepting_contributions(&context, &state, &zk_state, context.sender);
+    let contributions = contribution_variables(&zk_state);
+    assert_no_unpaid_commitment(&state, &contributions, context.sender);
+
+    let metadata = SecretVarType::Contribution {
+        owner: context.sender,
+        timestamp: context.block_production_time,
+        transfer_amount: 0,
+    };
+
+    let input_def = ZkInputDef::with_metadata(None, metadata);
+    (state, vec![], input_def)
+}
+
+/// Add contribution and transfer tokens in one transaction - Requires a prior token approval
+/// The transfer is charged once the variable is inputted, so the callback can mark it paid
+#[zk_on_secret_input(shortname = 0x41)]
+fn add_contribution_with_transfer(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    amount: u32,
+) -> (
+    ContractState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, Sbu32>,
+) {
+    assert_accepting_contributions(&context, &state, &zk_state, context.sender);
+    let contributions = contribution_variables(&zk_state);
+    assert_no_unpaid_commitment(&state, &contributions, context.sender);
+    assert_contribution_fits(&state, TokenUnits(amount));
+
+    let metadata = SecretVarType::Contribution {
+        owner: context.sender,
+        timestamp: context.block_production_time,
+        transfer_amount: amount,
+    };
+
+    let input_def = ZkInputDef::with_metadata(
+        Some(ShortnameZkVariableInputted::from_u32(
+            CONTRIBUTION_INPUTTED_SHORTNAME,
+        )),
+        metadata,
+    );
+    (state, vec![], input_def)
+}
+
+/// Contribute on behalf of a beneficiary - The sender pays, the beneficiary owns the contribution
+/// Only the beneficiary can withdraw it, so a payer can neither reclaim nor redirect a gift. The
+/// commitment stays unpaid until the callback confirms the payer's transfer for its variable.
+#[zk_on_secret_input(shortname = 0x44)]
+fn contribute_tokens_for(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    beneficiary: Address,
+    amount: u32,
+) -> (
+    ContractState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, Sbu32>,
+) {
+    assert_accepting_contributions(&context, &state, &zk_state, beneficiary);
+    let contributions = contribution_variables(&zk_state);
+    assert_no_unpaid_commitment(&state, &contributions, beneficiary);
+    assert_no_unpaid_commitment(&state, &contributions, context.sender);
+
+    assert_contribution_fits(&state, TokenUnits(amount));
+    assert_ne!(
+        beneficiary, context.contract_address,
+        "Campaign cannot be its own beneficiary"
+    );
+
+    let metadata = SecretVarType::Contribution {
+        owner: beneficiary,
+        timestamp: context.block_production_time,
+        transfer_amount: amount,
+    };
+
+    let input_def = ZkInputDef::with_metadata(
+        Some(ShortnameZkVariableInputted::from_u32(
+            CONTRIBUTION_INPUTTED_SHORTNAME,
+        )),
+        metadata,
+    );
+    (state, vec![], input_def)
+}
+
+/// Contribution inputted - Charges the submitter for a contribution that carries its transfer
+/// amount, tied to the new variable so the callback marks exactly this one as paid
+#[zk_on_variable_inputted(shortname = 0x45)]
+fn contribution_inputted(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    variable_id: SecretVarId,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let variable = zk_state
+        .get_variable(variable_id)
+        .expect("Inputted variable should exist");
+    let SecretVarType::Contribution {
+        owner,
+        transfer_amount,
+        ..
+    } = variable.metadata
+    else {
+        return (state, vec![], vec![]);
+    };
+    if transfer_amount == 0 {
+        return (state, vec![], vec![]);
+    }
+
+    let event_group = contribution_transfer(
+        &context,
+        &state,
+        variable.owner,
+        owner,
+        variable_id,
+        TokenUnits(transfer_amount),
+    );
+    (state, vec![event_group], vec![])
+}
+
+/// Token transfer
+#[action(shortname = 0x07, zk = true)]
+fn contribute_tokens(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    amount: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Contributions can only be made when campaign is active"
+    );
+
+    assert!(!state.contributions_paused, "Contributions are paused");
+    assert_before_contribution_deadline(&context, &state);
+    assert!(amount > 0, "Contribution amount must be greater than 0");
+
+    // Pays for the sender's unpaid add_contribution commitment
+    let variable_id = zk_state
+        .secret_variables
+        .iter()
+        .find(|(variable_id, var)| {
+            var.owner == context.sender
+                && !state.paid_contributions.contains(variable_id)
+                && matches!(&var.metadata, SecretVarType::Contribution { owner, transfer_amount: 0, .. } if *owner == context.sender)
+        })
+        .map(|(variable_id, _)| *variable_id)
+        .expect("Must create contribution commitment first");
+
+    let event_group = contribution_transfer(
+        &context,
+        &state,
+        context.sender,
+        context.sender,
+        variable_id,
+        TokenUnits(amount),
+    );
+    (state, vec![event_group])
+}
+
+/// Contribution callback - Credits a confirmed transfer and forwards the fee, or deletes the
+/// unpaid variable of a failed one
+#[callback(shortname = 0x31, zk = true)]
+fn contribute_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    variable_id: u32,
+    amount: u32,
+    contributor: Address,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let variable_id = SecretVarId::new(variable_id);
+    let variable_id = zk_state.get_variable(variable_id).map(|_| variable_id);
+    let zk_changes = settle_contribution_transfer(
+        &mut state,
+        callback_ctx.success,
+        variable_id,
+        contributor,
+        TokenUnits(amount),
+    );
+
+    // The fee was received together with the contribution, forward it to the owner
+    let mut events = vec![];
+    if callback_ctx.success && state.contribution_fee > 0 {
+        events.push(fee_transfer(&state, TokenUnits(state.contribution_fee)));
+    }
+
+    (state, events, zk_changes)
+}
+
+/// End campaign - Now creates 3 ZK variables for privacy-preserving withdrawal
+#[action(shortname = 0x01, zk = true)]
+fn end_campaign(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can end the campaign"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Campaign can only be ended from Active state"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+    assert!(
+        state.pending_withdrawals.is_empty(),
+        "Contribution withdrawals are still pending"
+    );
+
+    // Unpaid commitments are deleted before the sum, so only paid variables reach the computation
+    // and the revealed totals only count contributions that were actually paid
+    let unpaid_variables = unpaid_contribution_ids(&state, &contribution_variables(&zk_state));
+
+    let num_contributors = count_contributions(&zk_state) - unpaid_variables.len() as u32;
+    state.status = CampaignStatus::Computing {};
+    state.num_contributors = Some(num_contributors);
+
+    if num_contributors == 0 {
+        // No contributions, campaign automatically fails
+        state.status = CampaignStatus::Completed {};
+        state.completed_at = Some(context.block_production_time);
+        state.is_successful = false;
+        state.total_raised = None;
+        let events = completion_events(&state);
+        return (state, events, vec![]);
+    }
+
+    if state.max_possible_total < state.funding_target {
+        // Tokens received cannot reach the target, fail without the ZK computation.
+        // The bound is the sum of the public contribute_tokens amounts.
+        state.status = CampaignStatus::Completed {};
+        state.completed_at = Some(context.block_production_time);
+        state.is_successful = false;
+        state.total_raised = None;
+        let events = completion_events(&state);
+        return (state, events, vec![]);
+    }
+
+    let computation_change = threshold_computation(&context, &mut state);
+
+    let mut zk_changes = vec![];
+    if !unpaid_variables.is_empty() {
+        zk_changes.push(ZkStateChange::DeleteVariables {
+            variables: unpaid_variables,
+        });
+    }
+    zk_changes.push(computation_change);
+
+    (state, vec![], zk_changes)
+}
+
+/// Computation complete - Now handles 3 variables
+#[zk_on_compute_complete(shortname = 0x42)]
+fn threshold_check_complete(
+    context: ContractContext,
+    mut state: ContractState,
     _zk_state: ZkState<SecretVarType>,
     output_variables: Vec<SecretVarId>,
 ) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if !matches!(state.status, CampaignStatus::Computing {}) {
+        // Computation was aborted, ignore the late result
+        return (state, vec![], vec![]);
+    }
+
     if output_variables.len() >= 3 {
         // output_variables[0] = ThresholdCheckResult (1 if met, 0 if not)
-        // output_variables[1] = ConditionalTotal (total if met, 0 if not) - for public display
-        // output_variables[2] = ActualTotal (always real total) - for owner withdrawal
+        // output_variables[1] = ActualTotal (always real total) - for owner withdrawal
+        // output_variables[2] = ContributorCount (contributions above zero)
+
+        state.withdrawal_tracker_id = Some(output_variables[1]); // Private withdrawal
+
+        // With a reveal delay the outcome stays sealed until reveal_total opens it
+        if state.reveal_delay_millis > 0 {
+            state.result_computed_at = Some(context.block_production_time);
+            return (state, vec![], vec![]);
+        }
 
-        state.balance_tracker_id = Some(output_variables[1]); // Public display
-        state.withdrawal_tracker_id = Some(output_variables[2]); // Private withdrawal
+        state.reveal_phase = RevealPhase::Threshold {};
 
-        // Always reveal the threshold result (whether target was met)
+        // Always reveal the threshold result (whether target was met) and the contributor count
         (
             state,
             vec![],
             vec![ZkStateChange::OpenVariables {
-                variables: vec![output_variables[0]], // Reveal threshold result
+                variables: vec![output_variables[0], output_variables[2]],
             }],
         )
     } else {
         state.status = CampaignStatus::Completed {};
+        state.completed_at = Some(context.block_production_time);
         state.is_successful = false;
         state.total_raised = None;
-        (state, vec![], vec![])
+        let events = completion_events(&state);
+        (state, events, vec![])
     }
 }
 
+/// Analytics computation complete - Reveal its outputs
+#[zk_on_compute_complete(shortname = 0x43)]
+fn analytics_complete(
+    _context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    output_variables: Vec<SecretVarId>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    (
+        state,
+        vec![],
+        vec![ZkStateChange::OpenVariables {
+            variables: output_variables,
+        }],
+    )
+}
+
 /// Handle revelations - Enhanced for privacy-preserving withdrawal
 #[zk_on_variables_opened]
 fn handle_opened_variables(
-    _context: ContractContext,
+    context: ContractContext,
     mut state: ContractState,
     zk_state: ZkState<SecretVarType>,
     opened_variables: Vec<SecretVarId>,
@@ -300,96 +1206,180 @@ fn handle_opened_variables(
         return (state, vec![], vec![]);
     }
 
-    let opened_variable = zk_state.get_variable(opened_variables[0]).unwrap();
-    let variable_id = opened_variables[0];
-
-    if matches!(state.status, CampaignStatus::Computing {}) {
-        // First revelation: threshold check result
-        if let Some(threshold_data) = &opened_variable.data {
-            if threshold_data.len() >= 4 {
-                let threshold_bytes: [u8; 4] = threshold_data[0..4].try_into().unwrap_or([0u8; 4]);
-                let threshold_met = u32::from_le_bytes(threshold_bytes);
-
-                state.status = CampaignStatus::Completed {};
-
-                if threshold_met == 1 {
-                    // Threshold was met - campaign successful
-                    state.is_successful = true;
-
-                    // Reveal the conditional total for public display
-                    if let Some(balance_tracker_id) = state.balance_tracker_id {
-                        return (
-                            state,
-                            vec![],
-                            vec![ZkStateChange::OpenVariables {
-                                variables: vec![balance_tracker_id],
-                            }],
-                        );
-                    }
-                } else {
-                    // Threshold not met - campaign failed
-                    state.is_successful = false;
-                    state.total_raised = None; // Keep public total hidden
-                }
+    // The effective contributor count is revealed together with the threshold result,
+    // replacing the provisional count of commitments taken in end_campaign
+    for id in &opened_variables {
+        let Some(variable) = zk_state.get_variable(*id) else {
+            continue;
+        };
+        if matches!(variable.metadata, SecretVarType::ContributorCount { .. }) {
+            if let Some(count) = opened_u32(&variable.data) {
+                state.num_contributors = Some(count);
             }
         }
-
-        return (state, vec![], vec![]);
     }
 
-    // Check if this is the conditional total being revealed (for public display)
-    if let Some(balance_tracker_id) = state.balance_tracker_id {
-        if variable_id == balance_tracker_id {
-            if let Some(total_data) = &opened_variable.data {
-                if total_data.len() >= 4 {
-                    let total_bytes: [u8; 4] = total_data[0..4].try_into().unwrap_or([0u8; 4]);
-                    let total_amount = u32::from_le_bytes(total_bytes);
-
-                    // Set public total (will be 0 if campaign failed, real total if successful)
-                    state.total_raised = Some(total_amount);
+    // Analytics results are routed by their metadata, independent of the campaign lifecycle
+    let Some(first_variable) = opened_variables
+        .iter()
+        .find_map(|id| zk_state.get_variable(*id))
+    else {
+        return (state, vec![], vec![]);
+    };
+    match first_variable.metadata {
+        SecretVarType::MedianContribution { .. } => {
+            state.median_contribution = opened_u32(&first_variable.data);
+            return (state, vec![], vec![]);
+        }
+        SecretVarType::AverageContribution { .. } => {
+            state.average_contribution = opened_u32(&first_variable.data);
+            return (state, vec![], vec![]);
+        }
+        SecretVarType::ShareMultipleCheck { share_price } => {
+            state.share_price = Some(share_price);
+            state.all_contributions_whole_shares =
+                opened_u32(&first_variable.data).map(|all_valid| all_valid == 1);
+            return (state, vec![], vec![]);
+        }
+        SecretVarType::LimitCheck { per_person_limit } => {
+            state.per_person_limit = Some(per_person_limit);
+            state.all_within_limit =
+                opened_u32(&first_variable.data).map(|all_within| all_within == 1);
+            return (state, vec![], vec![]);
+        }
+        SecretVarType::LeaderboardEntry { .. } => {
+            let mut entries: Vec<(u8, Address)> = vec![];
+            for id in &opened_variables {
+                let Some(variable) = zk_state.get_variable(*id) else {
+                    continue;
+                };
+                let SecretVarType::LeaderboardEntry { rank } = variable.metadata else {
+                    continue;
+                };
+                // Zero marks a missing entry when there are fewer contributions than ranks
+                let contribution_owner = opened_u32(&variable.data)
+                    .filter(|raw_id| *raw_id > 0)
+                    .and_then(|raw_id| zk_state.get_variable(SecretVarId::new(raw_id)))
+                    .and_then(|contribution| match contribution.metadata {
+                        SecretVarType::Contribution { owner, .. } => Some(owner),
+                        _ => None,
+                    });
+                if let Some(owner) = contribution_owner {
+                    entries.push((rank, owner));
                 }
             }
+            entries.sort_by_key(|(rank, _)| *rank);
+            state.top_contributors = entries.into_iter().map(|(_, owner)| owner).collect();
             return (state, vec![], vec![]);
         }
+        _ => {}
     }
 
-    // Check if this is the actual total being revealed (for withdrawal)
-    if let Some(withdrawal_tracker_id) = state.withdrawal_tracker_id {
-        if variable_id == withdrawal_tracker_id && state.funds_withdrawn {
-            if let Some(withdrawal_data) = &opened_variable.data {
-                if withdrawal_data.len() >= 4 {
-                    let amount_bytes: [u8; 4] =
-                        withdrawal_data[0..4].try_into().unwrap_or([0u8; 4]);
-                    let tokens_to_withdraw = u32::from_le_bytes(amount_bytes);
-
-                    if tokens_to_withdraw > 0 {
-                        let withdraw_amount_wei = token_units_to_wei(tokens_to_withdraw);
-
-                        let mut event_group = EventGroup::builder();
-                        event_group
-                            .call(state.token_address, Shortname::from_u32(0x01))
-                            .argument(state.owner)
-                            .argument(withdraw_amount_wei)
-                            .done();
+    if matches!(state.status, CampaignStatus::Active {}) {
+        // Contributions opened by withdraw_contribution
+        return refund_opened_contributions(state, &zk_state, opened_variables);
+    }
 
-                        return (state, vec![event_group.build()], vec![]);
-                    }
-                }
-            }
+    // Lifecycle reveals are dispatched by the phase recorded when the variables were opened
+    let reveal_phase = std::mem::replace(&mut state.reveal_phase, RevealPhase::None {});
+    match reveal_phase {
+        RevealPhase::Threshold {} => {
+            threshold_revealed(context, state, &zk_state, &opened_variables)
         }
+        RevealPhase::Withdrawal {} => {
+            actual_total_revealed(state, opened_u32(&first_variable.data))
+        }
+        RevealPhase::None {} => (state, vec![], vec![]),
     }
-
-    (state, vec![], vec![])
 }
 
-/// Withdraw funds - Now uses separate withdrawal tracker for privacy
-#[action(shortname = 0x04, zk = true)]
-fn withdraw_funds(
+/// Threshold result revealed - Completes the campaign and publishes the total on success
+fn threshold_revealed(
     context: ContractContext,
     mut state: ContractState,
-    _zk_state: ZkState<SecretVarType>,
+    zk_state: &ZkState<SecretVarType>,
+    opened_variables: &[SecretVarId],
 ) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    assert_eq!(
+    let threshold_met = opened_variables
+        .iter()
+        .filter_map(|id| zk_state.get_variable(*id))
+        .find(|variable| {
+            matches!(
+                variable.metadata,
+                SecretVarType::ThresholdCheckResult { .. }
+            )
+        })
+        .and_then(|variable| opened_u32(&variable.data));
+    let Some(threshold_met) = threshold_met else {
+        return (state, vec![], vec![]);
+    };
+
+    state.status = CampaignStatus::Completed {};
+    state.completed_at = Some(context.block_production_time);
+
+    if threshold_met != 1 {
+        // Threshold not met - campaign failed
+        state.is_successful = false;
+        state.total_raised = None; // Keep public total hidden
+        let events = completion_events(&state);
+        return (state, events, vec![]);
+    }
+
+    // Threshold was met - campaign successful
+    state.is_successful = true;
+    publish_total(&context, state)
+}
+
+/// Publish the total of a successful campaign and pull the sponsor's match
+/// The public total is the funding target; the exact sum is only opened for the owner's withdrawal,
+/// so the public does not learn by how much the campaign was overfunded
+fn publish_total(
+    context: &ContractContext,
+    mut state: ContractState,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    state.total_raised = Some(state.funding_target);
+    state.total_raised_base = Some(
+        TokenUnits(state.funding_target)
+            .to_base_units(state.token_decimals)
+            .0,
+    );
+
+    let mut events = completion_events(&state);
+    match (state.matcher, state.total_raised) {
+        (Some(matcher), Some(total_raised)) if total_raised > 0 => {
+            events.push(matching_transfer(context, &state, matcher));
+        }
+        _ => state.matched_amount = Some(0),
+    }
+    (state, events, vec![])
+}
+
+/// Actual total revealed to the owner - Transfers the confirmed part of it
+fn actual_total_revealed(
+    mut state: ContractState,
+    revealed_total: Option<u32>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let tokens_to_withdraw = confirmed_withdrawal(&state, revealed_total.unwrap_or(0));
+
+    if tokens_to_withdraw > 0 {
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+        let event_group = withdrawal_transfer(&state, TokenUnits(tokens_to_withdraw));
+        return (state, vec![event_group], vec![]);
+    }
+
+    // Nothing was raised, so there is nothing to transfer
+    state.withdrawal_status = WithdrawalStatus::Withdrawn {};
+    (state, vec![], vec![])
+}
+
+/// Withdraw funds - Now uses separate withdrawal tracker for privacy
+#[action(shortname = 0x04, zk = true)]
+fn withdraw_funds(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
         context.sender, state.owner,
         "Only the owner can withdraw funds"
     );
@@ -398,16 +1388,44 @@ fn withdraw_funds(
         CampaignStatus::Completed {},
         "Campaign must be completed"
     );
-    assert!(!state.funds_withdrawn, "Funds have already been withdrawn");
+    assert_withdrawal_not_started(&state);
 
     // REMOVED: No longer require campaign to be successful for withdrawal
     // Owner can withdraw even from failed campaigns (but amount stays private from public)
 
-    let withdrawal_tracker_id = state
-        .withdrawal_tracker_id
-        .expect("Withdrawal tracker should exist after campaign completion");
+    assert!(
+        !(state.is_successful && state.matcher.is_some() && state.matched_amount.is_none()),
+        "Matching sponsor transfer is still pending"
+    );
+    assert_eq!(
+        state.reveal_phase,
+        RevealPhase::None {},
+        "Another reveal is in progress"
+    );
+
+    let Some(withdrawal_tracker_id) = state.withdrawal_tracker_id else {
+        // Completed without the ZK computation: pay out the tokens actually received
+        assert!(state.max_possible_total > 0, "No funds to withdraw");
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+        let event_group = withdrawal_transfer(&state, TokenUnits(state.max_possible_total));
+        return (state, vec![event_group], vec![]);
+    };
+
+    // Retry after a failed transfer: the actual total is already opened
+    let opened_total = zk_state
+        .get_variable(withdrawal_tracker_id)
+        .and_then(|variable| opened_u32(&variable.data));
+
+    if let Some(revealed_total) = opened_total {
+        let tokens_to_withdraw = confirmed_withdrawal(&state, revealed_total);
+        assert!(tokens_to_withdraw > 0, "No funds to withdraw");
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+        let event_group = withdrawal_transfer(&state, TokenUnits(tokens_to_withdraw));
+        return (state, vec![event_group], vec![]);
+    }
 
-    state.funds_withdrawn = true;
+    state.withdrawal_status = WithdrawalStatus::Revealing {};
+    state.reveal_phase = RevealPhase::Withdrawal {};
 
     // Open the actual total (private to owner, not revealed to public)
     (
@@ -418,3 +1436,1595 @@ fn withdraw_funds(
         }],
     )
 }
+
+/// Rejects a second withdraw_funds while the first is revealing or transferring, or once done
+fn assert_withdrawal_not_started(state: &ContractState) {
+    match state.withdrawal_status {
+        WithdrawalStatus::NotStarted {} => {}
+        WithdrawalStatus::Withdrawn {} => panic!("Funds have already been withdrawn"),
+        _ => panic!("Withdrawal is already in progress"),
+    }
+}
+
+/// Withdrawal callback - Allows the owner to retry if the token transfer failed
+#[callback(shortname = 0x32, zk = true)]
+fn withdraw_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    _tokens_withdrawn: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    settle_withdrawal(&mut state, callback_ctx.success);
+    (state, vec![], vec![])
+}
+
+/// Settle the withdrawal transfer - A failed transfer left the tokens in the contract, so the
+/// owner can call withdraw_funds again
+fn settle_withdrawal(state: &mut ContractState, succeeded: bool) {
+    state.withdrawal_status = if succeeded {
+        WithdrawalStatus::Withdrawn {}
+    } else {
+        WithdrawalStatus::NotStarted {}
+    };
+}
+
+/// Refund callback - A failed refund is credited back to the contributor, who can retry it with
+/// withdraw_contribution
+#[callback(shortname = 0x35, zk = true)]
+fn refund_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    contributor: Address,
+    refund: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if !callback_ctx.success {
+        credit_contribution(&mut state, contributor, TokenUnits(refund));
+    }
+    (state, vec![], vec![])
+}
+
+/// Fee callback - Records a fee that failed to reach the owner
+#[callback(shortname = 0x36, zk = true)]
+fn fee_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    fee: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    settle_fee_transfer(&mut state, callback_ctx.success, TokenUnits(fee));
+    (state, vec![], vec![])
+}
+
+/// Claim fees whose forward to the owner failed - A failed claim is recorded again
+#[action(shortname = 0x23, zk = true)]
+fn claim_fees(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(context.sender, state.owner, "Only owner can claim fees");
+    assert!(state.unclaimed_fees > 0, "No fees to claim");
+
+    let fee = TokenUnits(std::mem::take(&mut state.unclaimed_fees));
+    let event_group = fee_transfer(&state, fee);
+    (state, vec![event_group], vec![])
+}
+
+/// Matching callback - Settles the sponsor's match before the owner withdraws
+#[callback(shortname = 0x34, zk = true)]
+fn matching_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    match_amount: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if callback_ctx.success {
+        state.matched_amount = Some(match_amount);
+        state.max_possible_total = state
+            .max_possible_total
+            .checked_add(match_amount)
+            .expect("Match overflows the campaign total");
+    } else {
+        // Usually an insufficient allowance from the sponsor; settle at zero so the owner
+        // can still withdraw the contributions
+        state.matched_amount = Some(0);
+    }
+    (state, vec![], vec![])
+}
+
+/// Register a sponsor who matches the funding target up to match_cap on success
+/// The sponsor must approve the campaign for match_cap before the campaign completes
+#[action(shortname = 0x0E, zk = true)]
+fn set_matching_sponsor(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    matcher: Address,
+    match_cap: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can set the matching sponsor"
+    );
+    assert!(
+        matches!(
+            state.status,
+            CampaignStatus::Setup {} | CampaignStatus::Active {}
+        ),
+        "Matching sponsor can only be set before the campaign ends"
+    );
+    assert!(match_cap > 0, "Match cap must be greater than 0");
+
+    state.matcher = Some(matcher);
+    state.match_cap = match_cap;
+    (state, vec![], vec![])
+}
+
+/// Reveal the outcome - Opens the threshold result and contributor count once the reveal delay
+/// has elapsed, callable by anyone
+#[action(shortname = 0x0F, zk = true)]
+fn reveal_total(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        state.status,
+        CampaignStatus::Computing {},
+        "Outcome is only withheld while the campaign is computing"
+    );
+    assert_reveal_delay_elapsed(&context, &state);
+    assert_eq!(
+        state.reveal_phase,
+        RevealPhase::None {},
+        "Another reveal is in progress"
+    );
+
+    let outcome_variables: Vec<SecretVarId> = zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, var)| {
+            matches!(
+                var.metadata,
+                SecretVarType::ThresholdCheckResult { .. } | SecretVarType::ContributorCount { .. }
+            )
+        })
+        .map(|(variable_id, _)| *variable_id)
+        .collect();
+
+    state.reveal_phase = RevealPhase::Threshold {};
+    (
+        state,
+        vec![],
+        vec![ZkStateChange::OpenVariables {
+            variables: outcome_variables,
+        }],
+    )
+}
+
+/// The outcome of a finished computation stays sealed for reveal_delay_millis
+fn assert_reveal_delay_elapsed(context: &ContractContext, state: &ContractState) {
+    let result_computed_at = state
+        .result_computed_at
+        .expect("Outcome is not withheld by a reveal delay");
+    assert!(
+        context.block_production_time
+            >= result_computed_at.saturating_add(state.reveal_delay_millis),
+        "Reveal delay has not elapsed yet"
+    );
+}
+
+/// Abort a computation that has not finished within the timeout - The campaign fails and the
+/// owner can withdraw the confirmed contributions
+#[action(shortname = 0x0B, zk = true)]
+fn abort_computation(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can abort the computation"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Computing {},
+        "Only a computing campaign can be aborted"
+    );
+    assert!(
+        state.withdrawal_tracker_id.is_none(),
+        "Computation has finished, its outcome is waiting for reveal_total"
+    );
+    let started_at = state
+        .computation_started_at
+        .expect("Computation start should be recorded while computing");
+    assert!(
+        context.block_production_time >= started_at + COMPUTATION_TIMEOUT_MILLIS,
+        "Computation has not timed out yet"
+    );
+
+    state.status = CampaignStatus::Completed {};
+    state.completed_at = Some(context.block_production_time);
+    state.is_successful = false;
+    state.total_raised = None;
+    state.withdrawal_tracker_id = None;
+    state.reveal_phase = RevealPhase::None {};
+
+    let events = completion_events(&state);
+    (state, events, vec![])
+}
+
+/// Set or clear the success hook - The hook contract's action receives (total_raised: u32,
+/// num_contributors: u32)
+#[action(shortname = 0x1B, zk = true)]
+fn set_success_hook(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    on_success_contract: Option<Address>,
+    on_success_shortname: Option<u32>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can set the success hook"
+    );
+    assert!(
+        matches!(
+            state.status,
+            CampaignStatus::Setup {} | CampaignStatus::Active {}
+        ),
+        "Success hook can only be set before the campaign ends"
+    );
+    assert_eq!(
+        on_success_contract.is_some(),
+        on_success_shortname.is_some(),
+        "Success hook needs both a contract and a shortname"
+    );
+
+    state.on_success_contract = on_success_contract;
+    state.on_success_shortname = on_success_shortname;
+    (state, vec![], vec![])
+}
+
+/// Start campaign - Opens contributions once the owner has finished configuring the campaign
+#[action(shortname = 0x1D, zk = true)]
+fn start_campaign(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can start the campaign"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Setup {},
+        "Campaign can only be started from Setup state"
+    );
+
+    state.status = CampaignStatus::Active {};
+    (state, vec![], vec![])
+}
+
+/// Pause contributions - The campaign stays Active and can be resumed
+#[action(shortname = 0x1F, zk = true)]
+fn pause_contributions(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can pause contributions"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Contributions can only be paused while campaign is active"
+    );
+    assert!(
+        !state.contributions_paused,
+        "Contributions are already paused"
+    );
+
+    state.contributions_paused = true;
+    (state, vec![], vec![])
+}
+
+/// Resume contributions after a pause
+#[action(shortname = 0x20, zk = true)]
+fn resume_contributions(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can resume contributions"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Contributions can only be resumed while campaign is active"
+    );
+    assert!(state.contributions_paused, "Contributions are not paused");
+
+    state.contributions_paused = false;
+    (state, vec![], vec![])
+}
+
+/// Propose a new campaign owner - Takes effect once they accept
+#[action(shortname = 0x16, zk = true)]
+fn transfer_campaign_ownership(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    new_owner: Address,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can transfer ownership"
+    );
+    assert!(
+        matches!(
+            state.status,
+            CampaignStatus::Setup {} | CampaignStatus::Active {}
+        ),
+        "Ownership can only be transferred before the campaign ends"
+    );
+    assert_ne!(
+        new_owner, state.owner,
+        "New owner must differ from the owner"
+    );
+
+    state.pending_owner = Some(new_owner);
+    (state, vec![], vec![])
+}
+
+/// Accept a proposed ownership transfer
+#[action(shortname = 0x17, zk = true)]
+fn accept_campaign_ownership(
+    context: ContractContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        state.pending_owner,
+        Some(context.sender),
+        "Only the proposed owner can accept ownership"
+    );
+    assert!(
+        matches!(
+            state.status,
+            CampaignStatus::Setup {} | CampaignStatus::Active {}
+        ),
+        "Ownership can only be transferred before the campaign ends"
+    );
+
+    state.owner = context.sender;
+    state.pending_owner = None;
+    (state, vec![], vec![])
+}
+
+/// Re-run the threshold computation over the same contributions - Recovery for an implausible
+/// result, only before any funds have left the contract and before a success is made public
+#[action(shortname = 0x14, zk = true)]
+fn recompute(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can recompute the result"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Completed {},
+        "Only a completed campaign can be recomputed"
+    );
+    assert!(
+        state.withdrawal_tracker_id.is_some(),
+        "Campaign completed without a computation"
+    );
+    assert_eq!(
+        state.withdrawal_status,
+        WithdrawalStatus::NotStarted {},
+        "Cannot recompute after a withdrawal has started"
+    );
+    assert_eq!(
+        state.matched_amount.unwrap_or(0),
+        0,
+        "Cannot recompute after the sponsor match was received"
+    );
+    // A revealed success has already called the success hook and the factory
+    assert!(
+        state.total_raised.is_none(),
+        "Cannot recompute after the total has been revealed"
+    );
+    // The factory is told the outcome as soon as the computation finishes
+    assert!(
+        state.factory_address.is_none(),
+        "Cannot recompute after the factory was notified of the result"
+    );
+    assert_eq!(
+        state.reveal_phase,
+        RevealPhase::None {},
+        "Another reveal is in progress"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+
+    // Outputs of the previous run are replaced by the new ones
+    let previous_outputs: Vec<SecretVarId> = zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, var)| {
+            matches!(
+                var.metadata,
+                SecretVarType::ThresholdCheckResult { .. }
+                    | SecretVarType::ActualTotal { .. }
+                    | SecretVarType::ContributorCount { .. }
+            )
+        })
+        .map(|(variable_id, _)| *variable_id)
+        .collect();
+
+    state.is_successful = false;
+    state.total_raised = None;
+    state.total_raised_base = None;
+    state.matched_amount = None;
+    state.completed_at = None;
+    state.withdrawal_tracker_id = None;
+    state.result_computed_at = None;
+
+    let computation_change = threshold_computation(&context, &mut state);
+    (
+        state,
+        vec![],
+        vec![
+            ZkStateChange::DeleteVariables {
+                variables: previous_outputs,
+            },
+            computation_change,
+        ],
+    )
+}
+
+/// Relaunch a failed campaign with a new target and contribution deadline - Confirmed
+/// contributions carry over into the new round
+/// Consent is opt-out: contributors who do not want to take part again call
+/// withdraw_contribution while the new round is active, and the rest stay committed
+#[action(shortname = 0x22, zk = true)]
+fn relaunch(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    new_target: u32,
+    new_contribution_deadline: Option<i64>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can relaunch the campaign"
+    );
+    assert!(
+        matches!(state.status, CampaignStatus::Completed {}) && !state.is_successful,
+        "Only a failed campaign can be relaunched"
+    );
+    assert_eq!(
+        state.withdrawal_status,
+        WithdrawalStatus::NotStarted {},
+        "Cannot relaunch after a withdrawal has started"
+    );
+    assert_eq!(
+        state.reveal_phase,
+        RevealPhase::None {},
+        "Another reveal is in progress"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Relaunch must start from Waiting state"
+    );
+    assert!(new_target > 0, "Funding target must be greater than 0");
+    if let Some(new_contribution_deadline) = new_contribution_deadline {
+        assert!(
+            new_contribution_deadline > context.block_production_time,
+            "Contribution deadline must be in the future"
+        );
+    }
+
+    // Only the contributions survive; computation and analytics outputs belong to the old round
+    let previous_outputs: Vec<SecretVarId> = zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, var)| !matches!(var.metadata, SecretVarType::Contribution { .. }))
+        .map(|(variable_id, _)| *variable_id)
+        .collect();
+
+    state.status = CampaignStatus::Active {};
+    state.funding_target = new_target;
+    state.contribution_deadline = new_contribution_deadline;
+    state.total_raised = None;
+    state.total_raised_base = None;
+    state.num_contributors = None;
+    state.withdrawal_tracker_id = None;
+    state.computation_started_at = None;
+    state.result_computed_at = None;
+    state.completed_at = None;
+    state.matched_amount = None;
+    state.median_contribution = None;
+    state.share_price = None;
+    state.all_contributions_whole_shares = None;
+    state.top_contributors = vec![];
+    state.per_person_limit = None;
+    state.all_within_limit = None;
+    state.average_contribution = None;
+    state.contributions_paused = false;
+    state.pending_owner = None;
+
+    let mut zk_changes = vec![];
+    if !previous_outputs.is_empty() {
+        zk_changes.push(ZkStateChange::DeleteVariables {
+            variables: previous_outputs,
+        });
+    }
+    (state, vec![], zk_changes)
+}
+
+/// Check that every contribution is a whole multiple of the share price
+/// Only the aggregate flag is revealed, never which contribution was invalid
+#[action(shortname = 0x0D, zk = true)]
+fn validate_share_multiples(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    share_price: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can validate share multiples"
+    );
+    assert!(share_price > 0, "Share price must be greater than 0");
+    assert!(
+        !matches!(state.status, CampaignStatus::Computing {}),
+        "Cannot validate while the campaign is computing"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+
+    let computation_change = ZkStateChange::start_computation_with_inputs(
+        ShortnameZkComputation::from_u32(ZK_SHARE_MULTIPLE_CHECK_SHORTNAME),
+        vec![SecretVarType::ShareMultipleCheck { share_price }],
+        vec![share_price],
+        Some(ShortnameZkComputeComplete::from_u32(
+            ANALYTICS_COMPLETE_SHORTNAME,
+        )),
+    );
+
+    (state, vec![], vec![computation_change])
+}
+
+/// Check that no contribution exceeds a per-person limit
+/// Only the aggregate flag is revealed, never which contribution exceeded it. Each contribution
+/// is checked on its own, so repeat contributions by one address are not summed.
+#[action(shortname = 0x1A, zk = true)]
+fn validate_per_person_limit(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+    per_person_limit: u32,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can validate the per-person limit"
+    );
+    assert!(
+        per_person_limit > 0,
+        "Per-person limit must be greater than 0"
+    );
+    assert!(
+        !matches!(state.status, CampaignStatus::Computing {}),
+        "Cannot validate while the campaign is computing"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+
+    let computation_change = ZkStateChange::start_computation_with_inputs(
+        ShortnameZkComputation::from_u32(ZK_LIMIT_CHECK_SHORTNAME),
+        vec![SecretVarType::LimitCheck { per_person_limit }],
+        vec![per_person_limit],
+        Some(ShortnameZkComputeComplete::from_u32(
+            ANALYTICS_COMPLETE_SHORTNAME,
+        )),
+    );
+
+    (state, vec![], vec![computation_change])
+}
+
+/// Compute the median contribution for analytics - Only the median is revealed
+/// Selection compares every pair of contributions in ZK, so the cost grows quadratically and is
+/// bounded by max_contributors
+#[action(shortname = 0x0C, zk = true)]
+fn compute_median(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can compute the median"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Completed {},
+        "Median is only available after completion"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+
+    let num_contributions = count_contributions(&zk_state);
+    assert!(num_contributions > 0, "No contributions to analyze");
+
+    // Lower median: zero-based position in ascending order
+    let middle_rank = (num_contributions - 1) / 2;
+
+    let computation_change = ZkStateChange::start_computation_with_inputs(
+        ShortnameZkComputation::from_u32(ZK_MEDIAN_CONTRIBUTION_SHORTNAME),
+        vec![SecretVarType::MedianContribution { _placeholder: 0 }],
+        vec![middle_rank],
+        Some(ShortnameZkComputeComplete::from_u32(
+            ANALYTICS_COMPLETE_SHORTNAME,
+        )),
+    );
+
+    (state, vec![], vec![computation_change])
+}
+
+/// Reveal the owners of the largest contributions, amounts stay secret
+/// Ranks are per contribution, so a repeat contributor can hold several entries. Selection
+/// compares every pair of contributions in ZK and is bounded by max_contributors
+#[action(shortname = 0x19, zk = true)]
+fn compute_leaderboard(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can compute the leaderboard"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Completed {},
+        "Leaderboard is only available after completion"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+    assert!(
+        count_contributions(&zk_state) > 0,
+        "No contributions to analyze"
+    );
+
+    let output_metadata = (0..LEADERBOARD_SIZE)
+        .map(|rank| SecretVarType::LeaderboardEntry { rank })
+        .collect();
+
+    let computation_change = ZkStateChange::start_computation_with_inputs(
+        ShortnameZkComputation::from_u32(ZK_TOP_CONTRIBUTIONS_SHORTNAME),
+        output_metadata,
+        Vec::<u32>::new(),
+        Some(ShortnameZkComputeComplete::from_u32(
+            ANALYTICS_COMPLETE_SHORTNAME,
+        )),
+    );
+
+    (state, vec![], vec![computation_change])
+}
+
+/// Compute the average contribution for analytics, rounded down
+/// Opt-in for transparency: the average times the public contribution count gives the exact
+/// total, including for failed or overfunded campaigns whose total is otherwise withheld
+#[action(shortname = 0x1C, zk = true)]
+fn compute_average(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only owner can compute the average"
+    );
+    assert_eq!(
+        state.status,
+        CampaignStatus::Completed {},
+        "Average is only available after completion"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Computation must start from Waiting state"
+    );
+
+    let num_contributions = count_contributions(&zk_state);
+    assert!(num_contributions > 0, "No contributions to analyze");
+
+    let computation_change = ZkStateChange::start_computation_with_inputs(
+        ShortnameZkComputation::from_u32(ZK_AVERAGE_CONTRIBUTION_SHORTNAME),
+        vec![SecretVarType::AverageContribution { _placeholder: 0 }],
+        vec![num_contributions],
+        Some(ShortnameZkComputeComplete::from_u32(
+            ANALYTICS_COMPLETE_SHORTNAME,
+        )),
+    );
+
+    (state, vec![], vec![computation_change])
+}
+
+/// Withdraw the sender's contributions before the campaign ends
+/// Opens the sender's contribution variables; the refund follows once they are revealed, and
+/// repeat calls are rejected until then. A confirmed amount left without variables, e.g. after a
+/// failed refund, is refunded directly
+#[action(shortname = 0x0A, zk = true)]
+fn withdraw_contribution(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Contributions can only be withdrawn while campaign is active"
+    );
+    assert_eq!(
+        zk_state.calculation_state,
+        CalculationStatus::Waiting,
+        "Another computation or reveal is in progress"
+    );
+    assert_no_pending_withdrawal(&state, context.sender);
+
+    let contribution_variables: Vec<SecretVarId> = zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, var)| matches!(&var.metadata, SecretVarType::Contribution { owner, .. } if *owner == context.sender))
+        .map(|(variable_id, _)| *variable_id)
+        .collect();
+
+    if contribution_variables.is_empty() {
+        // Retry of a failed refund: the amount was already revealed, so no variable is left
+        let confirmed = state
+            .confirmed_contributions
+            .get(&context.sender)
+            .copied()
+            .unwrap_or(0);
+        assert!(confirmed > 0, "No contributions to withdraw");
+        let event_group = refund_transfer(&mut state, context.sender, TokenUnits(confirmed));
+        return (state, vec![event_group], vec![]);
+    }
+
+    state.pending_withdrawals.insert(context.sender);
+    (
+        state,
+        vec![],
+        vec![ZkStateChange::OpenVariables {
+            variables: contribution_variables,
+        }],
+    )
+}
+
+/// Variables opened for a withdrawal must be refunded before the contributor can withdraw again
+fn assert_no_pending_withdrawal(state: &ContractState, contributor: Address) {
+    assert!(
+        !state.pending_withdrawals.contains(&contributor),
+        "Contribution withdrawal is already pending"
+    );
+}
+
+/// Verify the sender with the attestation contract - Must complete before add_contribution,
+/// since a secret input cannot wait for a callback
+#[action(shortname = 0x09, zk = true)]
+fn verify_contributor(
+    context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let attestation_contract = state
+        .attestation_contract
+        .expect("Campaign does not require attestation");
+    assert_eq!(
+        state.status,
+        CampaignStatus::Active {},
+        "Contributors can only be verified when campaign is active"
+    );
+
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(
+            attestation_contract,
+            Shortname::from_u32(ATTESTATION_CHECK_SHORTNAME),
+        )
+        .argument(context.sender)
+        .done();
+
+    event_group
+        .with_callback(ShortnameCallback::from_u32(ATTESTATION_CALLBACK_SHORTNAME))
+        .argument(context.sender)
+        .done();
+
+    (state, vec![event_group.build()])
+}
+
+/// Attestation callback - Records the contributor as a verified unique human
+#[callback(shortname = 0x33, zk = true)]
+fn attestation_callback(
+    _ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    contributor: Address,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if !callback_ctx.success {
+        panic!("Contributor does not hold a unique-human attestation");
+    }
+    state.attested_contributors.insert(contributor);
+    (state, vec![], vec![])
+}
+
+/// Recover tokens other than the campaign token that were sent to the contract by mistake
+#[action(shortname = 0x08, zk = true)]
+fn recover_foreign_token(
+    context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+    token: Address,
+    to: Address,
+    amount: u128,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_eq!(
+        context.sender, state.owner,
+        "Only the owner can recover foreign tokens"
+    );
+    assert_ne!(
+        token, state.token_address,
+        "Campaign token cannot be recovered, use withdraw_funds"
+    );
+    assert!(amount > 0, "Recovery amount must be greater than 0");
+
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(token, Shortname::from_u32(0x01))
+        .argument(to)
+        .argument(amount)
+        .done();
+
+    (state, vec![event_group.build()])
+}
+
+/// Contributors ordered by their first contribution time - Amounts stay secret
+#[action(shortname = 0x10, zk = true)]
+fn get_contributors_ordered(
+    _context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut contributions: Vec<(i64, Address)> = zk_state
+        .secret_variables
+        .iter()
+        .filter_map(|(_, var)| match &var.metadata {
+            SecretVarType::Contribution {
+                owner, timestamp, ..
+            } => Some((*timestamp, *owner)),
+            _ => None,
+        })
+        .collect();
+    contributions.sort_by_key(|(timestamp, _)| *timestamp);
+
+    // Repeat contributors are listed once, at their earliest contribution
+    let mut contributors: Vec<Address> = vec![];
+    for (_, owner) in contributions {
+        if !contributors.contains(&owner) {
+            contributors.push(owner);
+        }
+    }
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(contributors);
+
+    (state, vec![event_group.build()])
+}
+
+/// Live number of contributions - Small counts are reported as the upper bound of their bucket
+/// (1-5 as 5, 6-20 as 20) so early contributors are not singled out. The raw variable count
+/// remains readable from the ZK state.
+#[action(shortname = 0x11, zk = true)]
+fn contributor_count(
+    _context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let count = match count_contributions(&zk_state) {
+        0 => 0,
+        1..=5 => 5,
+        6..=20 => 20,
+        count => count,
+    };
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(count);
+
+    (state, vec![event_group.build()])
+}
+
+/// Whether the caller is the campaign owner
+#[action(shortname = 0x12, zk = true)]
+fn is_owner(
+    context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(context.sender == state.owner);
+
+    (state, vec![event_group.build()])
+}
+
+/// Role of the caller - Owner takes precedence over Contributor
+#[action(shortname = 0x13, zk = true)]
+fn my_role(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let has_contributed = zk_state.secret_variables.iter().any(|(_, var)| {
+        matches!(&var.metadata, SecretVarType::Contribution { owner, .. } if *owner == context.sender)
+    });
+
+    let role = if context.sender == state.owner {
+        Role::Owner {}
+    } else if has_contributed {
+        Role::Contributor {}
+    } else {
+        Role::None {}
+    };
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(role);
+
+    (state, vec![event_group.build()])
+}
+
+/// Attestation of the caller's earliest contribution - Reveals inclusion, not the amount
+#[action(shortname = 0x15, zk = true)]
+fn get_contribution_attestation(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let timestamp = zk_state
+        .secret_variables
+        .iter()
+        .filter_map(|(_, var)| match &var.metadata {
+            SecretVarType::Contribution {
+                owner, timestamp, ..
+            } if *owner == context.sender => Some(*timestamp),
+            _ => None,
+        })
+        .min()
+        .expect("Caller has no contribution in this campaign");
+
+    let attestation = ContributionAttestation {
+        campaign_address: context.contract_address,
+        contributor: context.sender,
+        timestamp,
+    };
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(attestation);
+
+    (state, vec![event_group.build()])
+}
+
+/// Current ZK calculation state and secret variable count - Tells whether a Computing campaign
+/// is progressing or stuck
+#[action(shortname = 0x18, zk = true)]
+fn get_calculation_state(
+    _context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let calculation_state = match zk_state.calculation_state {
+        CalculationStatus::Waiting => 0,
+        CalculationStatus::Calculating => 1,
+        CalculationStatus::Output => 2,
+        CalculationStatus::MaliciousBehaviour => 3,
+    };
+    let diagnostics = CalculationDiagnostics {
+        calculation_state,
+        secret_variable_count: zk_state.secret_variables.len() as u32,
+    };
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(diagnostics);
+
+    (state, vec![event_group.build()])
+}
+
+/// Explorer-friendly view of the campaign state
+#[action(shortname = 0x1E, zk = true)]
+fn get_public_view(
+    _context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let status = match state.status {
+        CampaignStatus::Active {} => 0,
+        CampaignStatus::Computing {} => 1,
+        CampaignStatus::Completed {} => 2,
+        CampaignStatus::Setup {} => 3,
+    };
+    let withdrawal_status = match state.withdrawal_status {
+        WithdrawalStatus::NotStarted {} => 0,
+        WithdrawalStatus::Revealing {} => 1,
+        WithdrawalStatus::Transferring {} => 2,
+        WithdrawalStatus::Withdrawn {} => 3,
+    };
+
+    let view = PublicCampaignView {
+        owner: state.owner,
+        title: state.title.clone(),
+        token_address: state.token_address,
+        token_decimals: state.token_decimals,
+        funding_target: state.funding_target,
+        status,
+        is_successful: state.is_successful,
+        total_raised: state.total_raised.map_or(u64::MAX, u64::from),
+        num_contributors: state.num_contributors.unwrap_or(u32::MAX),
+        withdrawal_status,
+        completed_at: state.completed_at.unwrap_or(i64::MAX),
+    };
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(view);
+
+    (state, vec![event_group.build()])
+}
+
+/// Number of contribution variables with a confirmed token transfer - Compared with the number of
+/// contribution variables it shows commitments that were never paid
+#[action(shortname = 0x21, zk = true)]
+fn confirmed_contributor_count(
+    _context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(state.paid_contributions.len() as u32);
+
+    (state, vec![event_group.build()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pbc_contract_common::address::AddressType;
+    use pbc_contract_common::Hash;
+    use pbc_traits::ReadWriteState;
+
+    fn address(id: u8) -> Address {
+        Address {
+            address_type: AddressType::Account,
+            identifier: [id; 20],
+        }
+    }
+
+    fn context(sender: Address) -> ContractContext {
+        ContractContext {
+            contract_address: Address {
+                address_type: AddressType::ZkContract,
+                identifier: [0xCC; 20],
+            },
+            sender,
+            block_time: 100,
+            block_production_time: 1_000_000,
+            current_transaction: Hash { bytes: [0; 32] },
+            original_transaction: Hash { bytes: [0; 32] },
+        }
+    }
+
+    fn active_state() -> ContractState {
+        ContractState {
+            state_version: STATE_VERSION,
+            owner: address(1),
+            title: "Campaign".to_string(),
+            description: "Description".to_string(),
+            token_address: Address {
+                address_type: AddressType::PublicContract,
+                identifier: [0xAA; 20],
+            },
+            token_decimals: 18,
+            funding_target: 1_000,
+            max_contributors: DEFAULT_MAX_CONTRIBUTORS,
+            status: CampaignStatus::Active {},
+            total_raised: None,
+            total_raised_base: None,
+            num_contributors: None,
+            is_successful: false,
+            withdrawal_status: WithdrawalStatus::NotStarted {},
+            withdrawal_tracker_id: None,
+            factory_address: None,
+            attestation_contract: None,
+            attested_contributors: SortedVecSet::new(),
+            confirmed_contributions: SortedVecMap::new(),
+            max_possible_total: 0,
+            computation_started_at: None,
+            median_contribution: None,
+            share_price: None,
+            all_contributions_whole_shares: None,
+            matcher: None,
+            match_cap: 0,
+            matched_amount: None,
+            reveal_delay_millis: 0,
+            completed_at: None,
+            reveal_phase: RevealPhase::None {},
+            pending_owner: None,
+            contribution_fee: 0,
+            top_contributors: vec![],
+            per_person_limit: None,
+            all_within_limit: None,
+            on_success_contract: None,
+            on_success_shortname: None,
+            contribution_deadline: None,
+            average_contribution: None,
+            contribution_cooldown_millis: 0,
+            contributions_paused: false,
+            paid_contributions: SortedVecSet::new(),
+            pending_withdrawals: SortedVecSet::new(),
+            result_computed_at: None,
+            unclaimed_fees: 0,
+        }
+    }
+
+    #[test]
+    fn state_round_trips_with_version_first() {
+        let mut state = active_state();
+        state.contribution_deadline = Some(2_000_000);
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+        credit_contribution(&mut state, address(2), TokenUnits(300));
+
+        let mut bytes = vec![];
+        state.state_write_to(&mut bytes).unwrap();
+        assert_eq!(bytes[0], STATE_VERSION);
+
+        let read = ContractState::state_read_from(&mut bytes.as_slice());
+        assert_eq!(read.state_version, STATE_VERSION);
+        assert_eq!(read.owner, state.owner);
+        assert_eq!(read.title, state.title);
+        assert_eq!(read.status, state.status);
+        assert_eq!(read.withdrawal_status, state.withdrawal_status);
+        assert_eq!(read.confirmed_contributions.get(&address(2)), Some(&300));
+        assert_eq!(read.contribution_deadline, state.contribution_deadline);
+        assert_eq!(read.contributions_paused, state.contributions_paused);
+    }
+
+    #[test]
+    fn to_base_units_is_identity_at_token_unit_decimals() {
+        assert_eq!(
+            TokenUnits(123_456).to_base_units(TOKEN_UNIT_DECIMALS),
+            BaseUnits(123_456)
+        );
+        assert_eq!(
+            TokenUnits(u32::MAX).to_base_units(TOKEN_UNIT_DECIMALS),
+            BaseUnits(u32::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn to_base_units_scales_by_extra_decimals() {
+        assert_eq!(
+            TokenUnits(1_500_000).to_base_units(18),
+            BaseUnits(1_500_000_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn to_base_units_fits_max_amount_at_max_decimals() {
+        assert_eq!(
+            TokenUnits(u32::MAX).to_base_units(MAX_TOKEN_DECIMALS),
+            BaseUnits(u32::MAX as u128 * 10u128.pow(24))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Token decimals out of range for base unit conversion")]
+    fn to_base_units_rejects_decimals_below_token_units() {
+        TokenUnits(1).to_base_units(TOKEN_UNIT_DECIMALS - 1);
+    }
+
+    #[test]
+    fn cooldown_allows_contribution_after_window() {
+        let mut state = active_state();
+        state.contribution_cooldown_millis = 60_000;
+        let context = context(address(2));
+
+        assert_cooldown_elapsed(&context, &state, None);
+        assert_cooldown_elapsed(
+            &context,
+            &state,
+            Some(context.block_production_time - 60_000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution cooldown has not elapsed yet")]
+    fn cooldown_rejects_contribution_within_window() {
+        let mut state = active_state();
+        state.contribution_cooldown_millis = 60_000;
+        let context = context(address(2));
+
+        assert_cooldown_elapsed(
+            &context,
+            &state,
+            Some(context.block_production_time - 59_999),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution cooldown has not elapsed yet")]
+    fn cooldown_saturates_instead_of_overflowing() {
+        let mut state = active_state();
+        state.contribution_cooldown_millis = i64::MAX;
+        let context = context(address(2));
+
+        assert_cooldown_elapsed(&context, &state, Some(1));
+    }
+
+    #[test]
+    fn credit_contribution_adds_to_confirmed_and_total() {
+        let mut state = active_state();
+        credit_contribution(&mut state, address(2), TokenUnits(300));
+        credit_contribution(&mut state, address(2), TokenUnits(200));
+        credit_contribution(&mut state, address(3), TokenUnits(50));
+
+        assert_eq!(state.confirmed_contributions.get(&address(2)), Some(&500));
+        assert_eq!(state.confirmed_contributions.get(&address(3)), Some(&50));
+        assert_eq!(state.max_possible_total, 550);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution would overflow the campaign total")]
+    fn credit_contribution_rejects_total_overflow() {
+        let mut state = active_state();
+        state.max_possible_total = u32::MAX;
+        credit_contribution(&mut state, address(2), TokenUnits(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution amount must be greater than 0")]
+    fn contribution_transfer_rejects_zero_amount() {
+        let state = active_state();
+        contribution_transfer(&context(address(2)), &state, address(2), TokenUnits(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution would overflow the campaign total")]
+    fn contribution_transfer_rejects_total_overflow() {
+        let mut state = active_state();
+        state.max_possible_total = u32::MAX - 10;
+        contribution_transfer(&context(address(2)), &state, address(2), TokenUnits(11));
+    }
+
+    #[test]
+    #[should_panic(expected = "No funds to withdraw")]
+    fn withdrawal_transfer_rejects_zero_amount() {
+        let state = active_state();
+        withdrawal_transfer(&state, TokenUnits(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Withdrawal exceeds the tokens held by the campaign")]
+    fn withdrawal_transfer_rejects_more_than_held() {
+        let mut state = active_state();
+        state.max_possible_total = 500;
+        withdrawal_transfer(&state, TokenUnits(501));
+    }
+
+    #[test]
+    fn refund_transfer_debits_the_refund() {
+        let mut state = active_state();
+        credit_contribution(&mut state, address(2), TokenUnits(300));
+        credit_contribution(&mut state, address(3), TokenUnits(100));
+
+        refund_transfer(&mut state, address(2), TokenUnits(300));
+
+        assert_eq!(state.confirmed_contributions.get(&address(2)), None);
+        assert_eq!(state.max_possible_total, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Refund exceeds the confirmed contribution")]
+    fn refund_transfer_rejects_more_than_confirmed() {
+        let mut state = active_state();
+        credit_contribution(&mut state, address(2), TokenUnits(300));
+        refund_transfer(&mut state, address(2), TokenUnits(301));
+    }
+
+    fn contribution(raw_id: u32, submitter: Address, owner: Address) -> ContributionVariable {
+        ContributionVariable {
+            variable_id: SecretVarId::new(raw_id),
+            submitter,
+            owner,
+        }
+    }
+
+    #[test]
+    fn contributions_up_to_max_contributors_are_accepted() {
+        let mut state = active_state();
+        state.max_contributors = 2;
+        confirm_contribution(
+            &mut state,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+
+        assert_contribution_slot_available(&state);
+    }
+
+    #[test]
+    #[should_panic(expected = "Campaign has reached its maximum number of contributions")]
+    fn contribution_beyond_max_contributors_is_rejected() {
+        let mut state = active_state();
+        state.max_contributors = 2;
+        for raw_id in 1..=2 {
+            confirm_contribution(
+                &mut state,
+                Some(SecretVarId::new(raw_id)),
+                address(2),
+                TokenUnits(100),
+            );
+        }
+
+        assert_contribution_slot_available(&state);
+    }
+
+    #[test]
+    fn deleted_variable_is_credited_but_takes_no_slot() {
+        let mut state = active_state();
+        confirm_contribution(&mut state, None, address(2), TokenUnits(100));
+
+        assert_eq!(state.confirmed_contributions.get(&address(2)), Some(&100));
+        assert_eq!(state.paid_contributions.len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Previous contribution must be paid before committing another")]
+    fn unpaid_commitment_blocks_confirmed_contributor() {
+        let mut state = active_state();
+        confirm_contribution(
+            &mut state,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+        let contributions = [
+            contribution(1, address(2), address(2)),
+            contribution(2, address(2), address(2)),
+        ];
+
+        assert_no_unpaid_commitment(&state, &contributions, address(2));
+    }
+
+    #[test]
+    fn paid_commitments_do_not_block_contributor() {
+        let mut state = active_state();
+        confirm_contribution(
+            &mut state,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+        let contributions = [
+            contribution(1, address(2), address(2)),
+            contribution(2, address(3), address(3)),
+        ];
+
+        assert_no_unpaid_commitment(&state, &contributions, address(2));
+    }
+
+    #[test]
+    fn unpaid_commitment_of_confirmed_contributor_is_excluded_from_the_sum() {
+        let mut state = active_state();
+        confirm_contribution(
+            &mut state,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+        let contributions = [
+            contribution(1, address(2), address(2)),
+            contribution(2, address(2), address(2)),
+        ];
+
+        assert!(state.confirmed_contributions.contains_key(&address(2)));
+        assert_eq!(
+            unpaid_contribution_ids(&state, &contributions),
+            vec![SecretVarId::new(2)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Previous contribution must be paid before committing another")]
+    fn unpaid_gift_blocks_its_beneficiary() {
+        let state = active_state();
+        let contributions = [contribution(1, address(3), address(2))];
+
+        assert_no_unpaid_commitment(&state, &contributions, address(2));
+    }
+
+    #[test]
+    fn failed_transfer_deletes_the_unpaid_variable() {
+        let mut state = active_state();
+        let zk_changes = settle_contribution_transfer(
+            &mut state,
+            false,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+
+        let [ZkStateChange::DeleteVariables { variables }] = zk_changes.as_slice() else {
+            panic!("Expected the unpaid variable to be deleted");
+        };
+        assert_eq!(variables, &vec![SecretVarId::new(1)]);
+        assert!(!state.paid_contributions.contains(&SecretVarId::new(1)));
+    }
+
+    #[test]
+    fn failed_transfer_keeps_a_paid_variable() {
+        let mut state = active_state();
+        confirm_contribution(
+            &mut state,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+
+        let zk_changes = settle_contribution_transfer(
+            &mut state,
+            false,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+
+        assert!(zk_changes.is_empty());
+        assert!(state.paid_contributions.contains(&SecretVarId::new(1)));
+    }
+
+    #[test]
+    fn failed_contribution_transfer_credits_nothing() {
+        let mut state = active_state();
+        settle_contribution_transfer(
+            &mut state,
+            false,
+            Some(SecretVarId::new(1)),
+            address(2),
+            TokenUnits(100),
+        );
+
+        assert_eq!(state.confirmed_contributions.get(&address(2)), None);
+        assert_eq!(state.max_possible_total, 0);
+    }
+
+    #[test]
+    fn failed_withdrawal_can_be_retried() {
+        let mut state = active_state();
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+
+        settle_withdrawal(&mut state, false);
+
+        assert_eq!(state.withdrawal_status, WithdrawalStatus::NotStarted {});
+    }
+
+    #[test]
+    fn successful_withdrawal_is_final() {
+        let mut state = active_state();
+        state.withdrawal_status = WithdrawalStatus::Transferring {};
+
+        settle_withdrawal(&mut state, true);
+
+        assert_eq!(state.withdrawal_status, WithdrawalStatus::Withdrawn {});
+    }
+
+    #[test]
+    #[should_panic(expected = "Withdrawal is already in progress")]
+    fn duplicate_withdrawal_during_reveal_is_rejected() {
+        let mut state = active_state();
+        state.withdrawal_status = WithdrawalStatus::Revealing {};
+
+        assert_withdrawal_not_started(&state);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contribution withdrawal is already pending")]
+    fn repeated_contribution_withdrawal_is_rejected() {
+        let mut state = active_state();
+        state.pending_withdrawals.insert(address(2));
+
+        assert_no_pending_withdrawal(&state, address(2));
+    }
+
+    #[test]
+    fn other_contributors_can_withdraw_during_a_pending_withdrawal() {
+        let mut state = active_state();
+        state.pending_withdrawals.insert(address(2));
+
+        assert_no_pending_withdrawal(&state, address(3));
+    }
+
+    #[test]
+    fn published_total_is_the_funding_target() {
+        let mut state = active_state();
+        state.status = CampaignStatus::Completed {};
+        state.is_successful = true;
+
+        let (state, _, zk_changes) = publish_total(&context(address(2)), state);
+
+        assert_eq!(state.total_raised, Some(1_000));
+        assert_eq!(state.total_raised_base, Some(1_000_000_000_000_000));
+        assert_eq!(state.matched_amount, Some(0));
+        assert!(zk_changes.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Reveal delay has not elapsed yet")]
+    fn outcome_is_withheld_during_the_reveal_delay() {
+        let mut state = active_state();
+        let context = context(address(2));
+        state.reveal_delay_millis = 60_000;
+        state.result_computed_at = Some(context.block_production_time - 59_999);
+
+        assert_reveal_delay_elapsed(&context, &state);
+    }
+
+    #[test]
+    fn outcome_can_be_revealed_after_the_reveal_delay() {
+        let mut state = active_state();
+        let context = context(address(2));
+        state.reveal_delay_millis = 60_000;
+        state.result_computed_at = Some(context.block_production_time - 60_000);
+
+        assert_reveal_delay_elapsed(&context, &state);
+    }
+
+    #[test]
+    fn failed_fee_transfer_is_owed_to_the_owner() {
+        let mut state = active_state();
+        settle_fee_transfer(&mut state, false, TokenUnits(5));
+        settle_fee_transfer(&mut state, false, TokenUnits(5));
+        settle_fee_transfer(&mut state, true, TokenUnits(5));
+
+        assert_eq!(state.unclaimed_fees, 10);
+    }
+}
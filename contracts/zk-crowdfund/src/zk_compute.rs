@@ -4,7 +4,8 @@ use pbc_zk::*;
 const CONTRIBUTION_VARIABLE_KIND: u8 = 0u8;
 
 /// Privacy-preserving ZK computation with separate variables for public display and private withdrawal
-/// Returns (threshold_met, conditional_total, actual_total) - exactly 3 variables
+/// Returns (threshold_met, actual_total, contributor_count) - exactly 3 variables
+/// A successful campaign publishes the funding target as its total, so no public total is computed
 #[zk_compute(shortname = 0x61)]
 pub fn threshold_check_with_privacy_preserving_withdrawal(
     funding_target: u32,
@@ -12,8 +13,9 @@ pub fn threshold_check_with_privacy_preserving_withdrawal(
     // Convert the public input u32 to Sbu32 for ZK operations
     let target_sbu32 = Sbu32::from(funding_target);
 
-    // Step 1: Sum all contribution variables
+    // Step 1: Sum all contribution variables, counting only those above zero
     let mut total_contributions: Sbu32 = Sbu32::from(0u32);
+    let mut contributor_count: Sbu32 = Sbu32::from(0u32);
 
     for variable_id in secret_variable_ids() {
         let metadata_kind = load_metadata::<u8>(variable_id);
@@ -21,6 +23,15 @@ pub fn threshold_check_with_privacy_preserving_withdrawal(
         if metadata_kind == CONTRIBUTION_VARIABLE_KIND {
             let contribution_amount: Sbu32 = load_sbi::<Sbu32>(variable_id);
             total_contributions = total_contributions + contribution_amount;
+
+            // Zero commitments add nothing to the sum and are left out of the count
+            let is_nonzero = contribution_amount > Sbu32::from(0u32);
+            contributor_count = contributor_count
+                + if is_nonzero {
+                    Sbu32::from(1u32)
+                } else {
+                    Sbu32::from(0u32)
+                };
         }
     }
 
@@ -33,21 +44,171 @@ pub fn threshold_check_with_privacy_preserving_withdrawal(
         Sbu32::from(0u32) // Threshold not met
     };
 
-    // Step 3: Conditional total for PUBLIC display
-    // Only reveal total publicly if threshold is met, otherwise return 0
-    let conditional_total: Sbu32 = if meets_threshold {
-        total_contributions
-    } else {
-        Sbu32::from(0u32) // Keep total hidden from public if threshold not met
-    };
-
-    // Step 4: Actual total for PRIVATE withdrawal
+    // Step 3: Actual total for PRIVATE withdrawal
     // Always available to owner for withdrawal, regardless of threshold
     let actual_total: Sbu32 = total_contributions;
 
     // Return exactly 3 results:
     // 1. Whether threshold was met (1 = yes, 0 = no) - ALWAYS revealed to public
-    // 2. Conditional total - ONLY revealed to public if threshold met, 0 otherwise
-    // 3. Actual total - ONLY revealed to owner for withdrawal, never shown to public
-    (threshold_met, conditional_total, actual_total)
+    // 2. Actual total - ONLY revealed to owner for withdrawal, never shown to public
+    // 3. Number of contributions above zero - ALWAYS revealed to public
+    (threshold_met, actual_total, contributor_count)
+}
+
+/// Median contribution for analytics, selected without sorting the secret amounts
+/// For each contribution, count how many contributions are below it and at or below it; the one
+/// whose range of sorted positions covers `middle_rank` is the median. Costs O(n^2) comparisons.
+#[zk_compute(shortname = 0x62)]
+pub fn median_contribution(middle_rank: u32) -> Sbu32 {
+    let rank = Sbu32::from(middle_rank);
+    let mut median: Sbu32 = Sbu32::from(0u32);
+
+    for candidate_id in secret_variable_ids() {
+        if load_metadata::<u8>(candidate_id) == CONTRIBUTION_VARIABLE_KIND {
+            let candidate: Sbu32 = load_sbi::<Sbu32>(candidate_id);
+            let mut below: Sbu32 = Sbu32::from(0u32);
+            let mut at_or_below: Sbu32 = Sbu32::from(0u32);
+
+            for other_id in secret_variable_ids() {
+                if load_metadata::<u8>(other_id) == CONTRIBUTION_VARIABLE_KIND {
+                    let other: Sbu32 = load_sbi::<Sbu32>(other_id);
+                    below = below
+                        + if other < candidate {
+                            Sbu32::from(1u32)
+                        } else {
+                            Sbu32::from(0u32)
+                        };
+                    at_or_below = at_or_below
+                        + if other <= candidate {
+                            Sbu32::from(1u32)
+                        } else {
+                            Sbu32::from(0u32)
+                        };
+                }
+            }
+
+            // The candidate occupies sorted positions below..at_or_below
+            median = if below <= rank {
+                if rank < at_or_below {
+                    candidate
+                } else {
+                    median
+                }
+            } else {
+                median
+            };
+        }
+    }
+
+    median
+}
+
+/// Mean contribution rounded down, `contribution_count` being the public number of contributions
+#[zk_compute(shortname = 0x66)]
+pub fn average_contribution(contribution_count: u32) -> Sbu32 {
+    let mut total_contributions: Sbu32 = Sbu32::from(0u32);
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == CONTRIBUTION_VARIABLE_KIND {
+            total_contributions = total_contributions + load_sbi::<Sbu32>(variable_id);
+        }
+    }
+
+    total_contributions / Sbu32::from(contribution_count)
+}
+
+/// Whether every contribution is a whole multiple of `share_price` (1 = all valid, 0 otherwise)
+/// The per-contribution checks are combined in ZK, so only the aggregate flag is revealed
+#[zk_compute(shortname = 0x63)]
+pub fn all_contributions_multiple_of(share_price: u32) -> Sbu32 {
+    let price = Sbu32::from(share_price);
+    let mut all_valid: Sbu32 = Sbu32::from(1u32);
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == CONTRIBUTION_VARIABLE_KIND {
+            let contribution_amount: Sbu32 = load_sbi::<Sbu32>(variable_id);
+            let is_multiple = (contribution_amount % price) == Sbu32::from(0u32);
+            all_valid = if is_multiple {
+                all_valid
+            } else {
+                Sbu32::from(0u32)
+            };
+        }
+    }
+
+    all_valid
+}
+
+/// Whether every contribution is at most `per_person_limit` (1 = all within, 0 otherwise)
+/// Only the aggregate flag is revealed; a 0 does not tell which contribution exceeded the limit
+#[zk_compute(shortname = 0x65)]
+pub fn all_contributions_at_most(per_person_limit: u32) -> Sbu32 {
+    let limit = Sbu32::from(per_person_limit);
+    let mut all_within: Sbu32 = Sbu32::from(1u32);
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == CONTRIBUTION_VARIABLE_KIND {
+            let contribution_amount: Sbu32 = load_sbi::<Sbu32>(variable_id);
+            all_within = if contribution_amount <= limit {
+                all_within
+            } else {
+                Sbu32::from(0u32)
+            };
+        }
+    }
+
+    all_within
+}
+
+/// Variable ids of the three largest contributions, largest first (0 when there are fewer)
+/// Each contribution's rank is the number of contributions above it, ties going to the lower id;
+/// like the median this costs O(n^2) comparisons
+#[zk_compute(shortname = 0x64)]
+pub fn top_contributions() -> (Sbu32, Sbu32, Sbu32) {
+    let mut first: Sbu32 = Sbu32::from(0u32);
+    let mut second: Sbu32 = Sbu32::from(0u32);
+    let mut third: Sbu32 = Sbu32::from(0u32);
+
+    for candidate_id in secret_variable_ids() {
+        if load_metadata::<u8>(candidate_id) == CONTRIBUTION_VARIABLE_KIND {
+            let candidate: Sbu32 = load_sbi::<Sbu32>(candidate_id);
+            let mut above: Sbu32 = Sbu32::from(0u32);
+
+            for other_id in secret_variable_ids() {
+                if load_metadata::<u8>(other_id) == CONTRIBUTION_VARIABLE_KIND {
+                    let other: Sbu32 = load_sbi::<Sbu32>(other_id);
+                    let ranks_above = if other_id.raw_id < candidate_id.raw_id {
+                        other >= candidate
+                    } else {
+                        other > candidate
+                    };
+                    above = above
+                        + if ranks_above {
+                            Sbu32::from(1u32)
+                        } else {
+                            Sbu32::from(0u32)
+                        };
+                }
+            }
+
+            let id = Sbu32::from(candidate_id.raw_id);
+            first = if above == Sbu32::from(0u32) {
+                id
+            } else {
+                first
+            };
+            second = if above == Sbu32::from(1u32) {
+                id
+            } else {
+                second
+            };
+            third = if above == Sbu32::from(2u32) {
+                id
+            } else {
+                third
+            };
+        }
+    }
+
+    (first, second, third)
 }
@@ -1,5 +1,9 @@
 import { BlockchainAddress } from "@partisiablockchain/abi-client";
-import { CampaignStatusD, ContractState } from "@/contracts/CrowdfundGenerated";
+import {
+  CampaignStatusD,
+  ContractState,
+  WithdrawalStatusD,
+} from "@/contracts/CrowdfundGenerated";
 
 export type CrowdfundAction =
   | "end_campaign"
@@ -17,7 +21,11 @@ export function checkPermission(
     case "end_campaign":
       return isOwner && state.status.discriminant === CampaignStatusD.Active;
     case "withdraw_funds":
-      return isOwner && state.status.discriminant === CampaignStatusD.Completed;
+      return (
+        isOwner &&
+        state.status.discriminant === CampaignStatusD.Completed &&
+        state.withdrawalStatus.discriminant === WithdrawalStatusD.NotStarted
+      );
     case "contribute_tokens":
       return true; // Anyone can contribute
     default:
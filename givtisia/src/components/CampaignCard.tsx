@@ -7,7 +7,10 @@ import {
   CardTitle,
 } from "@/components/ui/card";
 import { Crowdfunding } from "@/hooks/useCampaignContract";
-import { CampaignStatusD } from "@/contracts/CrowdfundGenerated";
+import {
+  CampaignStatusD,
+  WithdrawalStatusD,
+} from "@/contracts/CrowdfundGenerated";
 import { Progress } from "@/components/ui/progress";
 import { Button } from "@/components/ui/button";
 import { useState, useEffect } from "react";
@@ -126,7 +129,10 @@ export function CampaignCard({ campaign, campaignId }: CrowdfundingCardProps) {
   const canWithdraw =
     isOwner &&
     campaign.status.discriminant === CampaignStatusD.Completed &&
-    !campaign.fundsWithdrawn;
+    campaign.withdrawalStatus.discriminant === WithdrawalStatusD.NotStarted;
+
+  const fundsWithdrawn =
+    campaign.withdrawalStatus.discriminant === WithdrawalStatusD.Withdrawn;
 
   const showAdminActions =
     isOwner &&
@@ -182,7 +188,10 @@ export function CampaignCard({ campaign, campaignId }: CrowdfundingCardProps) {
     if (isSepoliaEth && userBalance !== null) {
       // Convert input amount to wei for precise comparison
       const inputTokenUnits = displayAmountToTokenUnits(amountNum);
-      const inputWei = tokenUnitsToWei(inputTokenUnits);
+      const inputWei = tokenUnitsToWei(
+        inputTokenUnits + campaign.contributionFee,
+        campaign.tokenDecimals
+      );
 
       if (inputWei > userBalance) {
         setAmountInputError(
@@ -207,6 +216,8 @@ export function CampaignCard({ campaign, campaignId }: CrowdfundingCardProps) {
         crowdfundingAddress: campaignId,
         amount: rawAmount,
         tokenAddress: campaign.tokenAddress.asString(),
+        tokenDecimals: campaign.tokenDecimals,
+        contributionFee: campaign.contributionFee,
       };
 
       const result = await contributeSecret(params);
@@ -282,6 +293,8 @@ export function CampaignCard({ campaign, campaignId }: CrowdfundingCardProps) {
         return "Computing";
       case CampaignStatusD.Completed:
         return "Ended";
+      case CampaignStatusD.Setup:
+        return "Not started";
       default:
         return "Unknown";
     }
@@ -492,7 +505,7 @@ export function CampaignCard({ campaign, campaignId }: CrowdfundingCardProps) {
               </Button>
             </div>
           )}
-          {isOwner && campaign.fundsWithdrawn && (
+          {isOwner && fundsWithdrawn && (
             <p className="text-xs text-muted-foreground ml-auto p-0.5 border-[1.5px] rounded-sm">
               💸 Funds withdrawn
             </p>
@@ -36,11 +36,14 @@ export class CrowdfundGenerated {
     this._client = client;
   }
   public deserializeContractState(_input: AbiInput): ContractState {
+    const stateVersion: number = _input.readU8();
     const owner: BlockchainAddress = _input.readAddress();
     const title: string = _input.readString();
     const description: string = _input.readString();
     const tokenAddress: BlockchainAddress = _input.readAddress();
+    const tokenDecimals: number = _input.readU8();
     const fundingTarget: number = _input.readU32();
+    const maxContributors: number = _input.readU32();
     const status: CampaignStatus = this.deserializeCampaignStatus(_input);
     let totalRaised: Option<number> = undefined;
     const totalRaised_isSome = _input.readBoolean();
@@ -48,6 +51,12 @@ export class CrowdfundGenerated {
       const totalRaised_option: number = _input.readU32();
       totalRaised = totalRaised_option;
     }
+    let totalRaisedBase: Option<BN> = undefined;
+    const totalRaisedBase_isSome = _input.readBoolean();
+    if (totalRaisedBase_isSome) {
+      const totalRaisedBase_option: BN = _input.readU128();
+      totalRaisedBase = totalRaisedBase_option;
+    }
     let numContributors: Option<number> = undefined;
     const numContributors_isSome = _input.readBoolean();
     if (numContributors_isSome) {
@@ -55,26 +64,228 @@ export class CrowdfundGenerated {
       numContributors = numContributors_option;
     }
     const isSuccessful: boolean = _input.readBoolean();
-    const fundsWithdrawn: boolean = _input.readBoolean();
-    let balanceTrackerId: Option<SecretVarId> = undefined;
-    const balanceTrackerId_isSome = _input.readBoolean();
-    if (balanceTrackerId_isSome) {
-      const balanceTrackerId_option: SecretVarId =
+    const withdrawalStatus: WithdrawalStatus =
+      this.deserializeWithdrawalStatus(_input);
+    let withdrawalTrackerId: Option<SecretVarId> = undefined;
+    const withdrawalTrackerId_isSome = _input.readBoolean();
+    if (withdrawalTrackerId_isSome) {
+      const withdrawalTrackerId_option: SecretVarId =
+        this.deserializeSecretVarId(_input);
+      withdrawalTrackerId = withdrawalTrackerId_option;
+    }
+    let factoryAddress: Option<BlockchainAddress> = undefined;
+    const factoryAddress_isSome = _input.readBoolean();
+    if (factoryAddress_isSome) {
+      const factoryAddress_option: BlockchainAddress = _input.readAddress();
+      factoryAddress = factoryAddress_option;
+    }
+    let attestationContract: Option<BlockchainAddress> = undefined;
+    const attestationContract_isSome = _input.readBoolean();
+    if (attestationContract_isSome) {
+      const attestationContract_option: BlockchainAddress =
+        _input.readAddress();
+      attestationContract = attestationContract_option;
+    }
+    const attestedContributors_setLength = _input.readI32();
+    const attestedContributors: BlockchainAddress[] = [];
+    for (
+      let attestedContributors_i = 0;
+      attestedContributors_i < attestedContributors_setLength;
+      attestedContributors_i++
+    ) {
+      const attestedContributors_elem: BlockchainAddress = _input.readAddress();
+      attestedContributors.push(attestedContributors_elem);
+    }
+    const confirmedContributions_mapLength = _input.readI32();
+    const confirmedContributions: Map<BlockchainAddress, number> = new Map();
+    for (
+      let confirmedContributions_i = 0;
+      confirmedContributions_i < confirmedContributions_mapLength;
+      confirmedContributions_i++
+    ) {
+      const confirmedContributions_key: BlockchainAddress =
+        _input.readAddress();
+      const confirmedContributions_value: number = _input.readU32();
+      confirmedContributions.set(
+        confirmedContributions_key,
+        confirmedContributions_value
+      );
+    }
+    const maxPossibleTotal: number = _input.readU32();
+    let computationStartedAt: Option<BN> = undefined;
+    const computationStartedAt_isSome = _input.readBoolean();
+    if (computationStartedAt_isSome) {
+      const computationStartedAt_option: BN = _input.readI64();
+      computationStartedAt = computationStartedAt_option;
+    }
+    let medianContribution: Option<number> = undefined;
+    const medianContribution_isSome = _input.readBoolean();
+    if (medianContribution_isSome) {
+      const medianContribution_option: number = _input.readU32();
+      medianContribution = medianContribution_option;
+    }
+    let sharePrice: Option<number> = undefined;
+    const sharePrice_isSome = _input.readBoolean();
+    if (sharePrice_isSome) {
+      const sharePrice_option: number = _input.readU32();
+      sharePrice = sharePrice_option;
+    }
+    let allContributionsWholeShares: Option<boolean> = undefined;
+    const allContributionsWholeShares_isSome = _input.readBoolean();
+    if (allContributionsWholeShares_isSome) {
+      const allContributionsWholeShares_option: boolean = _input.readBoolean();
+      allContributionsWholeShares = allContributionsWholeShares_option;
+    }
+    let matcher: Option<BlockchainAddress> = undefined;
+    const matcher_isSome = _input.readBoolean();
+    if (matcher_isSome) {
+      const matcher_option: BlockchainAddress = _input.readAddress();
+      matcher = matcher_option;
+    }
+    const matchCap: number = _input.readU32();
+    let matchedAmount: Option<number> = undefined;
+    const matchedAmount_isSome = _input.readBoolean();
+    if (matchedAmount_isSome) {
+      const matchedAmount_option: number = _input.readU32();
+      matchedAmount = matchedAmount_option;
+    }
+    const revealDelayMillis: BN = _input.readI64();
+    let completedAt: Option<BN> = undefined;
+    const completedAt_isSome = _input.readBoolean();
+    if (completedAt_isSome) {
+      const completedAt_option: BN = _input.readI64();
+      completedAt = completedAt_option;
+    }
+    const revealPhase: RevealPhase = this.deserializeRevealPhase(_input);
+    let pendingOwner: Option<BlockchainAddress> = undefined;
+    const pendingOwner_isSome = _input.readBoolean();
+    if (pendingOwner_isSome) {
+      const pendingOwner_option: BlockchainAddress = _input.readAddress();
+      pendingOwner = pendingOwner_option;
+    }
+    const contributionFee: number = _input.readU32();
+    const topContributors_vecLength = _input.readI32();
+    const topContributors: BlockchainAddress[] = [];
+    for (
+      let topContributors_i = 0;
+      topContributors_i < topContributors_vecLength;
+      topContributors_i++
+    ) {
+      const topContributors_elem: BlockchainAddress = _input.readAddress();
+      topContributors.push(topContributors_elem);
+    }
+    let perPersonLimit: Option<number> = undefined;
+    const perPersonLimit_isSome = _input.readBoolean();
+    if (perPersonLimit_isSome) {
+      const perPersonLimit_option: number = _input.readU32();
+      perPersonLimit = perPersonLimit_option;
+    }
+    let allWithinLimit: Option<boolean> = undefined;
+    const allWithinLimit_isSome = _input.readBoolean();
+    if (allWithinLimit_isSome) {
+      const allWithinLimit_option: boolean = _input.readBoolean();
+      allWithinLimit = allWithinLimit_option;
+    }
+    let onSuccessContract: Option<BlockchainAddress> = undefined;
+    const onSuccessContract_isSome = _input.readBoolean();
+    if (onSuccessContract_isSome) {
+      const onSuccessContract_option: BlockchainAddress = _input.readAddress();
+      onSuccessContract = onSuccessContract_option;
+    }
+    let onSuccessShortname: Option<number> = undefined;
+    const onSuccessShortname_isSome = _input.readBoolean();
+    if (onSuccessShortname_isSome) {
+      const onSuccessShortname_option: number = _input.readU32();
+      onSuccessShortname = onSuccessShortname_option;
+    }
+    let contributionDeadline: Option<BN> = undefined;
+    const contributionDeadline_isSome = _input.readBoolean();
+    if (contributionDeadline_isSome) {
+      const contributionDeadline_option: BN = _input.readI64();
+      contributionDeadline = contributionDeadline_option;
+    }
+    let averageContribution: Option<number> = undefined;
+    const averageContribution_isSome = _input.readBoolean();
+    if (averageContribution_isSome) {
+      const averageContribution_option: number = _input.readU32();
+      averageContribution = averageContribution_option;
+    }
+    const contributionCooldownMillis: BN = _input.readI64();
+    const contributionsPaused: boolean = _input.readBoolean();
+    const paidContributions_setLength = _input.readI32();
+    const paidContributions: SecretVarId[] = [];
+    for (
+      let paidContributions_i = 0;
+      paidContributions_i < paidContributions_setLength;
+      paidContributions_i++
+    ) {
+      const paidContributions_elem: SecretVarId =
         this.deserializeSecretVarId(_input);
-      balanceTrackerId = balanceTrackerId_option;
+      paidContributions.push(paidContributions_elem);
+    }
+    const pendingWithdrawals_setLength = _input.readI32();
+    const pendingWithdrawals: BlockchainAddress[] = [];
+    for (
+      let pendingWithdrawals_i = 0;
+      pendingWithdrawals_i < pendingWithdrawals_setLength;
+      pendingWithdrawals_i++
+    ) {
+      const pendingWithdrawals_elem: BlockchainAddress = _input.readAddress();
+      pendingWithdrawals.push(pendingWithdrawals_elem);
+    }
+    let resultComputedAt: Option<BN> = undefined;
+    const resultComputedAt_isSome = _input.readBoolean();
+    if (resultComputedAt_isSome) {
+      const resultComputedAt_option: BN = _input.readI64();
+      resultComputedAt = resultComputedAt_option;
     }
+    const unclaimedFees: number = _input.readU32();
     return {
+      stateVersion,
       owner,
       title,
       description,
       tokenAddress,
+      tokenDecimals,
       fundingTarget,
+      maxContributors,
       status,
       totalRaised,
+      totalRaisedBase,
       numContributors,
       isSuccessful,
-      fundsWithdrawn,
-      balanceTrackerId,
+      withdrawalStatus,
+      withdrawalTrackerId,
+      factoryAddress,
+      attestationContract,
+      attestedContributors,
+      confirmedContributions,
+      maxPossibleTotal,
+      computationStartedAt,
+      medianContribution,
+      sharePrice,
+      allContributionsWholeShares,
+      matcher,
+      matchCap,
+      matchedAmount,
+      revealDelayMillis,
+      completedAt,
+      revealPhase,
+      pendingOwner,
+      contributionFee,
+      topContributors,
+      perPersonLimit,
+      allWithinLimit,
+      onSuccessContract,
+      onSuccessShortname,
+      contributionDeadline,
+      averageContribution,
+      contributionCooldownMillis,
+      contributionsPaused,
+      paidContributions,
+      pendingWithdrawals,
+      resultComputedAt,
+      unclaimedFees,
     };
   }
   public deserializeCampaignStatus(_input: AbiInput): CampaignStatus {
@@ -85,6 +296,8 @@ export class CrowdfundGenerated {
       return this.deserializeCampaignStatusComputing(_input);
     } else if (discriminant === 2) {
       return this.deserializeCampaignStatusCompleted(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeCampaignStatusSetup(_input);
     }
     throw new Error("Unknown discriminant: " + discriminant);
   }
@@ -103,10 +316,106 @@ export class CrowdfundGenerated {
   ): CampaignStatusCompleted {
     return { discriminant: CampaignStatusD.Completed };
   }
+  public deserializeCampaignStatusSetup(_input: AbiInput): CampaignStatusSetup {
+    return { discriminant: CampaignStatusD.Setup };
+  }
+  public deserializeWithdrawalStatus(_input: AbiInput): WithdrawalStatus {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeWithdrawalStatusNotStarted(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeWithdrawalStatusRevealing(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeWithdrawalStatusTransferring(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeWithdrawalStatusWithdrawn(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeWithdrawalStatusNotStarted(
+    _input: AbiInput
+  ): WithdrawalStatusNotStarted {
+    return { discriminant: WithdrawalStatusD.NotStarted };
+  }
+  public deserializeWithdrawalStatusRevealing(
+    _input: AbiInput
+  ): WithdrawalStatusRevealing {
+    return { discriminant: WithdrawalStatusD.Revealing };
+  }
+  public deserializeWithdrawalStatusTransferring(
+    _input: AbiInput
+  ): WithdrawalStatusTransferring {
+    return { discriminant: WithdrawalStatusD.Transferring };
+  }
+  public deserializeWithdrawalStatusWithdrawn(
+    _input: AbiInput
+  ): WithdrawalStatusWithdrawn {
+    return { discriminant: WithdrawalStatusD.Withdrawn };
+  }
+  public deserializeRevealPhase(_input: AbiInput): RevealPhase {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeRevealPhaseNone(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeRevealPhaseThreshold(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeRevealPhaseWithdrawal(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeRevealPhaseNone(_input: AbiInput): RevealPhaseNone {
+    return { discriminant: RevealPhaseD.None };
+  }
+  public deserializeRevealPhaseThreshold(
+    _input: AbiInput
+  ): RevealPhaseThreshold {
+    return { discriminant: RevealPhaseD.Threshold };
+  }
+  public deserializeRevealPhaseWithdrawal(
+    _input: AbiInput
+  ): RevealPhaseWithdrawal {
+    return { discriminant: RevealPhaseD.Withdrawal };
+  }
   public deserializeSecretVarId(_input: AbiInput): SecretVarId {
     const rawId: number = _input.readU32();
     return { rawId };
   }
+  public deserializeCampaignSettings(_input: AbiInput): CampaignSettings {
+    const tokenDecimals: number = _input.readU8();
+    const maxContributors: number = _input.readU32();
+    let factoryAddress: Option<BlockchainAddress> = undefined;
+    const factoryAddress_isSome = _input.readBoolean();
+    if (factoryAddress_isSome) {
+      const factoryAddress_option: BlockchainAddress = _input.readAddress();
+      factoryAddress = factoryAddress_option;
+    }
+    let attestationContract: Option<BlockchainAddress> = undefined;
+    const attestationContract_isSome = _input.readBoolean();
+    if (attestationContract_isSome) {
+      const attestationContract_option: BlockchainAddress =
+        _input.readAddress();
+      attestationContract = attestationContract_option;
+    }
+    const revealDelayMillis: BN = _input.readI64();
+    const contributionFee: number = _input.readU32();
+    let contributionDeadline: Option<BN> = undefined;
+    const contributionDeadline_isSome = _input.readBoolean();
+    if (contributionDeadline_isSome) {
+      const contributionDeadline_option: BN = _input.readI64();
+      contributionDeadline = contributionDeadline_option;
+    }
+    const contributionCooldownMillis: BN = _input.readI64();
+    return {
+      tokenDecimals,
+      maxContributors,
+      factoryAddress,
+      attestationContract,
+      revealDelayMillis,
+      contributionFee,
+      contributionDeadline,
+      contributionCooldownMillis,
+    };
+  }
   public async getState(): Promise<ContractState> {
     const bytes = await this._client?.getContractStateBinary(this._address!);
     if (bytes === undefined) {
@@ -131,11 +440,233 @@ export class CrowdfundGenerated {
     return { discriminant: "contribute_tokens", amount };
   }
 
+  public deserializeRecoverForeignTokenAction(
+    _input: AbiInput
+  ): RecoverForeignTokenAction {
+    const token: BlockchainAddress = _input.readAddress();
+    const to: BlockchainAddress = _input.readAddress();
+    const amount: BN = _input.readU128();
+    return { discriminant: "recover_foreign_token", token, to, amount };
+  }
+
+  public deserializeVerifyContributorAction(
+    _input: AbiInput
+  ): VerifyContributorAction {
+    return { discriminant: "verify_contributor" };
+  }
+
+  public deserializeWithdrawContributionAction(
+    _input: AbiInput
+  ): WithdrawContributionAction {
+    return { discriminant: "withdraw_contribution" };
+  }
+
+  public deserializeAbortComputationAction(
+    _input: AbiInput
+  ): AbortComputationAction {
+    return { discriminant: "abort_computation" };
+  }
+
+  public deserializeComputeMedianAction(_input: AbiInput): ComputeMedianAction {
+    return { discriminant: "compute_median" };
+  }
+
+  public deserializeValidateShareMultiplesAction(
+    _input: AbiInput
+  ): ValidateShareMultiplesAction {
+    const sharePrice: number = _input.readU32();
+    return { discriminant: "validate_share_multiples", sharePrice };
+  }
+
+  public deserializeSetMatchingSponsorAction(
+    _input: AbiInput
+  ): SetMatchingSponsorAction {
+    const matcher: BlockchainAddress = _input.readAddress();
+    const matchCap: number = _input.readU32();
+    return { discriminant: "set_matching_sponsor", matcher, matchCap };
+  }
+
+  public deserializeRevealTotalAction(_input: AbiInput): RevealTotalAction {
+    return { discriminant: "reveal_total" };
+  }
+
+  public deserializeGetContributorsOrderedAction(
+    _input: AbiInput
+  ): GetContributorsOrderedAction {
+    return { discriminant: "get_contributors_ordered" };
+  }
+
+  public deserializeContributorCountAction(
+    _input: AbiInput
+  ): ContributorCountAction {
+    return { discriminant: "contributor_count" };
+  }
+
+  public deserializeIsOwnerAction(_input: AbiInput): IsOwnerAction {
+    return { discriminant: "is_owner" };
+  }
+
+  public deserializeMyRoleAction(_input: AbiInput): MyRoleAction {
+    return { discriminant: "my_role" };
+  }
+
+  public deserializeRecomputeAction(_input: AbiInput): RecomputeAction {
+    return { discriminant: "recompute" };
+  }
+
+  public deserializeGetContributionAttestationAction(
+    _input: AbiInput
+  ): GetContributionAttestationAction {
+    return { discriminant: "get_contribution_attestation" };
+  }
+
+  public deserializeTransferCampaignOwnershipAction(
+    _input: AbiInput
+  ): TransferCampaignOwnershipAction {
+    const newOwner: BlockchainAddress = _input.readAddress();
+    return { discriminant: "transfer_campaign_ownership", newOwner };
+  }
+
+  public deserializeAcceptCampaignOwnershipAction(
+    _input: AbiInput
+  ): AcceptCampaignOwnershipAction {
+    return { discriminant: "accept_campaign_ownership" };
+  }
+
+  public deserializeGetCalculationStateAction(
+    _input: AbiInput
+  ): GetCalculationStateAction {
+    return { discriminant: "get_calculation_state" };
+  }
+
+  public deserializeComputeLeaderboardAction(
+    _input: AbiInput
+  ): ComputeLeaderboardAction {
+    return { discriminant: "compute_leaderboard" };
+  }
+
+  public deserializeValidatePerPersonLimitAction(
+    _input: AbiInput
+  ): ValidatePerPersonLimitAction {
+    const perPersonLimit: number = _input.readU32();
+    return { discriminant: "validate_per_person_limit", perPersonLimit };
+  }
+
+  public deserializeSetSuccessHookAction(
+    _input: AbiInput
+  ): SetSuccessHookAction {
+    let onSuccessContract: Option<BlockchainAddress> = undefined;
+    const onSuccessContract_isSome = _input.readBoolean();
+    if (onSuccessContract_isSome) {
+      const onSuccessContract_option: BlockchainAddress = _input.readAddress();
+      onSuccessContract = onSuccessContract_option;
+    }
+    let onSuccessShortname: Option<number> = undefined;
+    const onSuccessShortname_isSome = _input.readBoolean();
+    if (onSuccessShortname_isSome) {
+      const onSuccessShortname_option: number = _input.readU32();
+      onSuccessShortname = onSuccessShortname_option;
+    }
+    return {
+      discriminant: "set_success_hook",
+      onSuccessContract,
+      onSuccessShortname,
+    };
+  }
+
+  public deserializeComputeAverageAction(
+    _input: AbiInput
+  ): ComputeAverageAction {
+    return { discriminant: "compute_average" };
+  }
+
+  public deserializeStartCampaignAction(_input: AbiInput): StartCampaignAction {
+    return { discriminant: "start_campaign" };
+  }
+
+  public deserializeGetPublicViewAction(_input: AbiInput): GetPublicViewAction {
+    return { discriminant: "get_public_view" };
+  }
+
+  public deserializePauseContributionsAction(
+    _input: AbiInput
+  ): PauseContributionsAction {
+    return { discriminant: "pause_contributions" };
+  }
+
+  public deserializeResumeContributionsAction(
+    _input: AbiInput
+  ): ResumeContributionsAction {
+    return { discriminant: "resume_contributions" };
+  }
+
+  public deserializeConfirmedContributorCountAction(
+    _input: AbiInput
+  ): ConfirmedContributorCountAction {
+    return { discriminant: "confirmed_contributor_count" };
+  }
+
+  public deserializeRelaunchAction(_input: AbiInput): RelaunchAction {
+    const newTarget: number = _input.readU32();
+    let newContributionDeadline: Option<BN> = undefined;
+    const newContributionDeadline_isSome = _input.readBoolean();
+    if (newContributionDeadline_isSome) {
+      const newContributionDeadline_option: BN = _input.readI64();
+      newContributionDeadline = newContributionDeadline_option;
+    }
+    return { discriminant: "relaunch", newTarget, newContributionDeadline };
+  }
+
+  public deserializeClaimFeesAction(_input: AbiInput): ClaimFeesAction {
+    return { discriminant: "claim_fees" };
+  }
+
   public deserializeContributeCallbackCallback(
     _input: AbiInput
   ): ContributeCallbackCallback {
-    const Amount: number = _input.readU32();
-    return { discriminant: "contribute_callback", Amount };
+    const variableId: number = _input.readU32();
+    const amount: number = _input.readU32();
+    const contributor: BlockchainAddress = _input.readAddress();
+    return {
+      discriminant: "contribute_callback",
+      variableId,
+      amount,
+      contributor,
+    };
+  }
+
+  public deserializeWithdrawCallbackCallback(
+    _input: AbiInput
+  ): WithdrawCallbackCallback {
+    const tokensWithdrawn: number = _input.readU32();
+    return { discriminant: "withdraw_callback", tokensWithdrawn };
+  }
+
+  public deserializeAttestationCallbackCallback(
+    _input: AbiInput
+  ): AttestationCallbackCallback {
+    const contributor: BlockchainAddress = _input.readAddress();
+    return { discriminant: "attestation_callback", contributor };
+  }
+
+  public deserializeMatchingCallbackCallback(
+    _input: AbiInput
+  ): MatchingCallbackCallback {
+    const matchAmount: number = _input.readU32();
+    return { discriminant: "matching_callback", matchAmount };
+  }
+
+  public deserializeRefundCallbackCallback(
+    _input: AbiInput
+  ): RefundCallbackCallback {
+    const contributor: BlockchainAddress = _input.readAddress();
+    const refund: number = _input.readU32();
+    return { discriminant: "refund_callback", contributor, refund };
+  }
+
+  public deserializeFeeCallbackCallback(_input: AbiInput): FeeCallbackCallback {
+    const fee: number = _input.readU32();
+    return { discriminant: "fee_callback", fee };
   }
 
   public deserializeInitializeInit(_input: AbiInput): InitializeInit {
@@ -143,38 +674,76 @@ export class CrowdfundGenerated {
     const description: string = _input.readString();
     const tokenAddress: BlockchainAddress = _input.readAddress();
     const fundingTarget: number = _input.readU32();
+    const settings: CampaignSettings = this.deserializeCampaignSettings(_input);
     return {
       discriminant: "initialize",
       title,
       description,
       tokenAddress,
       fundingTarget,
+      settings,
     };
   }
 }
 export interface ContractState {
+  stateVersion: number;
   owner: BlockchainAddress;
   title: string;
   description: string;
   tokenAddress: BlockchainAddress;
+  tokenDecimals: number;
   fundingTarget: number;
+  maxContributors: number;
   status: CampaignStatus;
   totalRaised: Option<number>;
+  totalRaisedBase: Option<BN>;
   numContributors: Option<number>;
   isSuccessful: boolean;
-  fundsWithdrawn: boolean;
-  balanceTrackerId: Option<SecretVarId>;
+  withdrawalStatus: WithdrawalStatus;
+  withdrawalTrackerId: Option<SecretVarId>;
+  factoryAddress: Option<BlockchainAddress>;
+  attestationContract: Option<BlockchainAddress>;
+  attestedContributors: BlockchainAddress[];
+  confirmedContributions: Map<BlockchainAddress, number>;
+  maxPossibleTotal: number;
+  computationStartedAt: Option<BN>;
+  medianContribution: Option<number>;
+  sharePrice: Option<number>;
+  allContributionsWholeShares: Option<boolean>;
+  matcher: Option<BlockchainAddress>;
+  matchCap: number;
+  matchedAmount: Option<number>;
+  revealDelayMillis: BN;
+  completedAt: Option<BN>;
+  revealPhase: RevealPhase;
+  pendingOwner: Option<BlockchainAddress>;
+  contributionFee: number;
+  topContributors: BlockchainAddress[];
+  perPersonLimit: Option<number>;
+  allWithinLimit: Option<boolean>;
+  onSuccessContract: Option<BlockchainAddress>;
+  onSuccessShortname: Option<number>;
+  contributionDeadline: Option<BN>;
+  averageContribution: Option<number>;
+  contributionCooldownMillis: BN;
+  contributionsPaused: boolean;
+  paidContributions: SecretVarId[];
+  pendingWithdrawals: BlockchainAddress[];
+  resultComputedAt: Option<BN>;
+  unclaimedFees: number;
 }
 
 export enum CampaignStatusD {
   Active = 0,
   Computing = 1,
   Completed = 2,
+  Setup = 3,
 }
 export type CampaignStatus =
   | CampaignStatusActive
   | CampaignStatusComputing
-  | CampaignStatusCompleted;
+  | CampaignStatusCompleted
+  | CampaignStatusSetup;
 
 export interface CampaignStatusActive {
   discriminant: CampaignStatusD.Active;
@@ -188,15 +757,103 @@ export interface CampaignStatusCompleted {
   discriminant: CampaignStatusD.Completed;
 }
 
+export interface CampaignStatusSetup {
+  discriminant: CampaignStatusD.Setup;
+}
+
+export enum WithdrawalStatusD {
+  NotStarted = 0,
+  Revealing = 1,
+  Transferring = 2,
+  Withdrawn = 3,
+}
+export type WithdrawalStatus =
+  | WithdrawalStatusNotStarted
+  | WithdrawalStatusRevealing
+  | WithdrawalStatusTransferring
+  | WithdrawalStatusWithdrawn;
+
+export interface WithdrawalStatusNotStarted {
+  discriminant: WithdrawalStatusD.NotStarted;
+}
+
+export interface WithdrawalStatusRevealing {
+  discriminant: WithdrawalStatusD.Revealing;
+}
+
+export interface WithdrawalStatusTransferring {
+  discriminant: WithdrawalStatusD.Transferring;
+}
+
+export interface WithdrawalStatusWithdrawn {
+  discriminant: WithdrawalStatusD.Withdrawn;
+}
+
+export enum RevealPhaseD {
+  None = 0,
+  Threshold = 1,
+  Withdrawal = 3,
+}
+export type RevealPhase =
+  | RevealPhaseNone
+  | RevealPhaseThreshold
+  | RevealPhaseWithdrawal;
+
+export interface RevealPhaseNone {
+  discriminant: RevealPhaseD.None;
+}
+
+export interface RevealPhaseThreshold {
+  discriminant: RevealPhaseD.Threshold;
+}
+
+export interface RevealPhaseWithdrawal {
+  discriminant: RevealPhaseD.Withdrawal;
+}
+
 export interface SecretVarId {
   rawId: number;
 }
 
+export interface CampaignSettings {
+  tokenDecimals: number;
+  maxContributors: number;
+  factoryAddress: Option<BlockchainAddress>;
+  attestationContract: Option<BlockchainAddress>;
+  revealDelayMillis: BN;
+  contributionFee: number;
+  contributionDeadline: Option<BN>;
+  contributionCooldownMillis: BN;
+}
+function serializeCampaignSettings(
+  _out: AbiOutput,
+  _value: CampaignSettings
+): void {
+  _out.writeU8(_value.tokenDecimals);
+  _out.writeU32(_value.maxContributors);
+  _out.writeBoolean(_value.factoryAddress !== undefined);
+  if (_value.factoryAddress !== undefined) {
+    _out.writeAddress(_value.factoryAddress);
+  }
+  _out.writeBoolean(_value.attestationContract !== undefined);
+  if (_value.attestationContract !== undefined) {
+    _out.writeAddress(_value.attestationContract);
+  }
+  _out.writeI64(_value.revealDelayMillis);
+  _out.writeU32(_value.contributionFee);
+  _out.writeBoolean(_value.contributionDeadline !== undefined);
+  if (_value.contributionDeadline !== undefined) {
+    _out.writeI64(_value.contributionDeadline);
+  }
+  _out.writeI64(_value.contributionCooldownMillis);
+}
+
 export function initialize(
   title: string,
   description: string,
   tokenAddress: BlockchainAddress,
-  fundingTarget: number
+  fundingTarget: number,
+  settings: CampaignSettings
 ): Buffer {
   return AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("ffffffff0f", "hex"));
@@ -204,6 +861,7 @@ export function initialize(
     _out.writeString(description);
     _out.writeAddress(tokenAddress);
     _out.writeU32(fundingTarget);
+    serializeCampaignSettings(_out, settings);
   });
 }
 
@@ -229,6 +887,236 @@ export function contributeTokens(amount: number): Buffer {
   });
 }
 
+export function recoverForeignToken(
+  token: BlockchainAddress,
+  to: BlockchainAddress,
+  amount: BN
+): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("08", "hex"));
+    _out.writeAddress(token);
+    _out.writeAddress(to);
+    _out.writeU128(amount);
+  });
+}
+
+export function verifyContributor(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("09", "hex"));
+  });
+}
+
+export function withdrawContribution(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0a", "hex"));
+  });
+}
+
+export function abortComputation(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0b", "hex"));
+  });
+}
+
+export function computeMedian(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0c", "hex"));
+  });
+}
+
+export function validateShareMultiples(sharePrice: number): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0d", "hex"));
+    _out.writeU32(sharePrice);
+  });
+}
+
+export function setMatchingSponsor(
+  matcher: BlockchainAddress,
+  matchCap: number
+): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0e", "hex"));
+    _out.writeAddress(matcher);
+    _out.writeU32(matchCap);
+  });
+}
+
+export function revealTotal(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0f", "hex"));
+  });
+}
+
+export function getContributorsOrdered(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("10", "hex"));
+  });
+}
+
+export function contributorCount(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("11", "hex"));
+  });
+}
+
+export function isOwner(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("12", "hex"));
+  });
+}
+
+export function myRole(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("13", "hex"));
+  });
+}
+
+export function recompute(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("14", "hex"));
+  });
+}
+
+export function getContributionAttestation(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("15", "hex"));
+  });
+}
+
+export function transferCampaignOwnership(newOwner: BlockchainAddress): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("16", "hex"));
+    _out.writeAddress(newOwner);
+  });
+}
+
+export function acceptCampaignOwnership(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("17", "hex"));
+  });
+}
+
+export function getCalculationState(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("18", "hex"));
+  });
+}
+
+export function computeLeaderboard(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("19", "hex"));
+  });
+}
+
+export function validatePerPersonLimit(perPersonLimit: number): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1a", "hex"));
+    _out.writeU32(perPersonLimit);
+  });
+}
+
+export function setSuccessHook(
+  onSuccessContract: Option<BlockchainAddress>,
+  onSuccessShortname: Option<number>
+): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1b", "hex"));
+    _out.writeBoolean(onSuccessContract !== undefined);
+    if (onSuccessContract !== undefined) {
+      _out.writeAddress(onSuccessContract);
+    }
+    _out.writeBoolean(onSuccessShortname !== undefined);
+    if (onSuccessShortname !== undefined) {
+      _out.writeU32(onSuccessShortname);
+    }
+  });
+}
+
+export function computeAverage(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1c", "hex"));
+  });
+}
+
+export function startCampaign(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1d", "hex"));
+  });
+}
+
+export function getPublicView(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1e", "hex"));
+  });
+}
+
+export function pauseContributions(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("1f", "hex"));
+  });
+}
+
+export function resumeContributions(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("20", "hex"));
+  });
+}
+
+export function confirmedContributorCount(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("21", "hex"));
+  });
+}
+
+export function relaunch(
+  newTarget: number,
+  newContributionDeadline: Option<BN>
+): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("22", "hex"));
+    _out.writeU32(newTarget);
+    _out.writeBoolean(newContributionDeadline !== undefined);
+    if (newContributionDeadline !== undefined) {
+      _out.writeI64(newContributionDeadline);
+    }
+  });
+}
+
+export function claimFees(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("23", "hex"));
+  });
+}
+
 export function addContribution(): SecretInputBuilder<number> {
   const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("40", "hex"));
@@ -240,6 +1128,36 @@ export function addContribution(): SecretInputBuilder<number> {
   return new SecretInputBuilder(_publicRpc, _secretInput);
 }
 
+export function addContributionWithTransfer(
+  amount: number
+): SecretInputBuilder<number> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("41", "hex"));
+    _out.writeU32(amount);
+  });
+  const _secretInput = (secret_input_lambda: number): CompactBitArray =>
+    AbiBitOutput.serialize((_out) => {
+      _out.writeU32(secret_input_lambda);
+    });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
+export function contributeTokensFor(
+  beneficiary: BlockchainAddress,
+  amount: number
+): SecretInputBuilder<number> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("44", "hex"));
+    _out.writeAddress(beneficiary);
+    _out.writeU32(amount);
+  });
+  const _secretInput = (secret_input_lambda: number): CompactBitArray =>
+    AbiBitOutput.serialize((_out) => {
+      _out.writeU32(secret_input_lambda);
+    });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
 export function deserializeState(state: StateWithClient): ContractState;
 export function deserializeState(bytes: Buffer): ContractState;
 export function deserializeState(
@@ -269,7 +1187,35 @@ export function deserializeState(
 export type Action =
   | EndCampaignAction
   | WithdrawFundsAction
-  | ContributeTokensAction;
+  | ContributeTokensAction
+  | RecoverForeignTokenAction
+  | VerifyContributorAction
+  | WithdrawContributionAction
+  | AbortComputationAction
+  | ComputeMedianAction
+  | ValidateShareMultiplesAction
+  | SetMatchingSponsorAction
+  | RevealTotalAction
+  | GetContributorsOrderedAction
+  | ContributorCountAction
+  | IsOwnerAction
+  | MyRoleAction
+  | RecomputeAction
+  | GetContributionAttestationAction
+  | TransferCampaignOwnershipAction
+  | AcceptCampaignOwnershipAction
+  | GetCalculationStateAction
+  | ComputeLeaderboardAction
+  | ValidatePerPersonLimitAction
+  | SetSuccessHookAction
+  | ComputeAverageAction
+  | StartCampaignAction
+  | GetPublicViewAction
+  | PauseContributionsAction
+  | ResumeContributionsAction
+  | ConfirmedContributorCountAction
+  | RelaunchAction
+  | ClaimFeesAction;
 
 export interface EndCampaignAction {
   discriminant: "end_campaign";
@@ -281,6 +1227,102 @@ export interface ContributeTokensAction {
   discriminant: "contribute_tokens";
   amount: number;
 }
+export interface RecoverForeignTokenAction {
+  discriminant: "recover_foreign_token";
+  token: BlockchainAddress;
+  to: BlockchainAddress;
+  amount: BN;
+}
+export interface VerifyContributorAction {
+  discriminant: "verify_contributor";
+}
+export interface WithdrawContributionAction {
+  discriminant: "withdraw_contribution";
+}
+export interface AbortComputationAction {
+  discriminant: "abort_computation";
+}
+export interface ComputeMedianAction {
+  discriminant: "compute_median";
+}
+export interface ValidateShareMultiplesAction {
+  discriminant: "validate_share_multiples";
+  sharePrice: number;
+}
+export interface SetMatchingSponsorAction {
+  discriminant: "set_matching_sponsor";
+  matcher: BlockchainAddress;
+  matchCap: number;
+}
+export interface RevealTotalAction {
+  discriminant: "reveal_total";
+}
+export interface GetContributorsOrderedAction {
+  discriminant: "get_contributors_ordered";
+}
+export interface ContributorCountAction {
+  discriminant: "contributor_count";
+}
+export interface IsOwnerAction {
+  discriminant: "is_owner";
+}
+export interface MyRoleAction {
+  discriminant: "my_role";
+}
+export interface RecomputeAction {
+  discriminant: "recompute";
+}
+export interface GetContributionAttestationAction {
+  discriminant: "get_contribution_attestation";
+}
+export interface TransferCampaignOwnershipAction {
+  discriminant: "transfer_campaign_ownership";
+  newOwner: BlockchainAddress;
+}
+export interface AcceptCampaignOwnershipAction {
+  discriminant: "accept_campaign_ownership";
+}
+export interface GetCalculationStateAction {
+  discriminant: "get_calculation_state";
+}
+export interface ComputeLeaderboardAction {
+  discriminant: "compute_leaderboard";
+}
+export interface ValidatePerPersonLimitAction {
+  discriminant: "validate_per_person_limit";
+  perPersonLimit: number;
+}
+export interface SetSuccessHookAction {
+  discriminant: "set_success_hook";
+  onSuccessContract: Option<BlockchainAddress>;
+  onSuccessShortname: Option<number>;
+}
+export interface ComputeAverageAction {
+  discriminant: "compute_average";
+}
+export interface StartCampaignAction {
+  discriminant: "start_campaign";
+}
+export interface GetPublicViewAction {
+  discriminant: "get_public_view";
+}
+export interface PauseContributionsAction {
+  discriminant: "pause_contributions";
+}
+export interface ResumeContributionsAction {
+  discriminant: "resume_contributions";
+}
+export interface ConfirmedContributorCountAction {
+  discriminant: "confirmed_contributor_count";
+}
+export interface RelaunchAction {
+  discriminant: "relaunch";
+  newTarget: number;
+  newContributionDeadline: Option<BN>;
+}
+export interface ClaimFeesAction {
+  discriminant: "claim_fees";
+}
 export function deserializeAction(bytes: Buffer): Action {
   const input = AbiByteInput.createBigEndian(bytes);
   input.readU8();
@@ -292,15 +1334,100 @@ export function deserializeAction(bytes: Buffer): Action {
     return contract.deserializeWithdrawFundsAction(input);
   } else if (shortname === "07") {
     return contract.deserializeContributeTokensAction(input);
+  } else if (shortname === "08") {
+    return contract.deserializeRecoverForeignTokenAction(input);
+  } else if (shortname === "09") {
+    return contract.deserializeVerifyContributorAction(input);
+  } else if (shortname === "0a") {
+    return contract.deserializeWithdrawContributionAction(input);
+  } else if (shortname === "0b") {
+    return contract.deserializeAbortComputationAction(input);
+  } else if (shortname === "0c") {
+    return contract.deserializeComputeMedianAction(input);
+  } else if (shortname === "0d") {
+    return contract.deserializeValidateShareMultiplesAction(input);
+  } else if (shortname === "0e") {
+    return contract.deserializeSetMatchingSponsorAction(input);
+  } else if (shortname === "0f") {
+    return contract.deserializeRevealTotalAction(input);
+  } else if (shortname === "10") {
+    return contract.deserializeGetContributorsOrderedAction(input);
+  } else if (shortname === "11") {
+    return contract.deserializeContributorCountAction(input);
+  } else if (shortname === "12") {
+    return contract.deserializeIsOwnerAction(input);
+  } else if (shortname === "13") {
+    return contract.deserializeMyRoleAction(input);
+  } else if (shortname === "14") {
+    return contract.deserializeRecomputeAction(input);
+  } else if (shortname === "15") {
+    return contract.deserializeGetContributionAttestationAction(input);
+  } else if (shortname === "16") {
+    return contract.deserializeTransferCampaignOwnershipAction(input);
+  } else if (shortname === "17") {
+    return contract.deserializeAcceptCampaignOwnershipAction(input);
+  } else if (shortname === "18") {
+    return contract.deserializeGetCalculationStateAction(input);
+  } else if (shortname === "19") {
+    return contract.deserializeComputeLeaderboardAction(input);
+  } else if (shortname === "1a") {
+    return contract.deserializeValidatePerPersonLimitAction(input);
+  } else if (shortname === "1b") {
+    return contract.deserializeSetSuccessHookAction(input);
+  } else if (shortname === "1c") {
+    return contract.deserializeComputeAverageAction(input);
+  } else if (shortname === "1d") {
+    return contract.deserializeStartCampaignAction(input);
+  } else if (shortname === "1e") {
+    return contract.deserializeGetPublicViewAction(input);
+  } else if (shortname === "1f") {
+    return contract.deserializePauseContributionsAction(input);
+  } else if (shortname === "20") {
+    return contract.deserializeResumeContributionsAction(input);
+  } else if (shortname === "21") {
+    return contract.deserializeConfirmedContributorCountAction(input);
+  } else if (shortname === "22") {
+    return contract.deserializeRelaunchAction(input);
+  } else if (shortname === "23") {
+    return contract.deserializeClaimFeesAction(input);
   }
   throw new Error("Illegal shortname: " + shortname);
 }
 
-export type Callback = ContributeCallbackCallback;
+export type Callback =
+  | ContributeCallbackCallback
+  | WithdrawCallbackCallback
+  | AttestationCallbackCallback
+  | MatchingCallbackCallback
+  | RefundCallbackCallback
+  | FeeCallbackCallback;
 
 export interface ContributeCallbackCallback {
   discriminant: "contribute_callback";
-  Amount: number;
+  variableId: number;
+  amount: number;
+  contributor: BlockchainAddress;
+}
+export interface WithdrawCallbackCallback {
+  discriminant: "withdraw_callback";
+  tokensWithdrawn: number;
+}
+export interface AttestationCallbackCallback {
+  discriminant: "attestation_callback";
+  contributor: BlockchainAddress;
+}
+export interface MatchingCallbackCallback {
+  discriminant: "matching_callback";
+  matchAmount: number;
+}
+export interface RefundCallbackCallback {
+  discriminant: "refund_callback";
+  contributor: BlockchainAddress;
+  refund: number;
+}
+export interface FeeCallbackCallback {
+  discriminant: "fee_callback";
+  fee: number;
 }
 export function deserializeCallback(bytes: Buffer): Callback {
   const input = AbiByteInput.createBigEndian(bytes);
@@ -308,6 +1435,16 @@ export function deserializeCallback(bytes: Buffer): Callback {
   const contract = new CrowdfundGenerated(undefined, undefined);
   if (shortname === "31") {
     return contract.deserializeContributeCallbackCallback(input);
+  } else if (shortname === "32") {
+    return contract.deserializeWithdrawCallbackCallback(input);
+  } else if (shortname === "33") {
+    return contract.deserializeAttestationCallbackCallback(input);
+  } else if (shortname === "34") {
+    return contract.deserializeMatchingCallbackCallback(input);
+  } else if (shortname === "35") {
+    return contract.deserializeRefundCallbackCallback(input);
+  } else if (shortname === "36") {
+    return contract.deserializeFeeCallbackCallback(input);
   }
   throw new Error("Illegal shortname: " + shortname);
 }
@@ -320,6 +1457,7 @@ export interface InitializeInit {
   description: string;
   tokenAddress: BlockchainAddress;
   fundingTarget: number;
+  settings: CampaignSettings;
 }
 export function deserializeInit(bytes: Buffer): Init {
   const input = AbiByteInput.createBigEndian(bytes);
@@ -171,8 +171,16 @@ export function useCrowdfundingContract() {
       crowdfundingAddress: string;
       amount: number;
       tokenAddress: string;
+      tokenDecimals: number;
+      contributionFee: number;
     }) => {
-      const { crowdfundingAddress, amount, tokenAddress } = params;
+      const {
+        crowdfundingAddress,
+        amount,
+        tokenAddress,
+        tokenDecimals,
+        contributionFee,
+      } = params;
       if (!account) throw new Error("Wallet not connected");
 
       // Check current allowance
@@ -182,7 +190,11 @@ export function useCrowdfundingContract() {
         crowdfundingAddress
       );
 
-      const weiAmount = BigInt(amount);
+      // The contract transfers the contribution fee together with the amount
+      const weiAmount = tokenUnitsToWei(
+        amount + contributionFee,
+        tokenDecimals
+      );
       if (currentAllowance < weiAmount) {
         // Need approval
         await approveTokens(tokenAddress, crowdfundingAddress, weiAmount);
@@ -205,8 +217,16 @@ export function useCrowdfundingContract() {
       crowdfundingAddress: string;
       amount: number;
       tokenAddress: string;
+      tokenDecimals: number;
+      contributionFee: number;
     }) => {
-      const { crowdfundingAddress, amount, tokenAddress } = params;
+      const {
+        crowdfundingAddress,
+        amount,
+        tokenAddress,
+        tokenDecimals,
+        contributionFee,
+      } = params;
       if (!account) throw new Error("Wallet not connected");
 
       // Check current allowance
@@ -216,7 +236,11 @@ export function useCrowdfundingContract() {
         crowdfundingAddress
       );
 
-      const weiAmount = BigInt(amount);
+      // The contract transfers the contribution fee together with the amount
+      const weiAmount = tokenUnitsToWei(
+        amount + contributionFee,
+        tokenDecimals
+      );
       if (currentAllowance < weiAmount) {
         // Need approval
         await approveTokens(tokenAddress, crowdfundingAddress, weiAmount);
@@ -369,16 +393,22 @@ export function useContribute() {
       crowdfundingAddress,
       amount,
       tokenAddress,
+      tokenDecimals,
+      contributionFee,
     }: {
       crowdfundingAddress: string;
       amount: number;
       tokenAddress: string;
+      tokenDecimals: number;
+      contributionFee: number;
     }): Promise<TransactionResult> => {
       try {
         const txn = await crowdfundingContract.contributeWithApproval({
           crowdfundingAddress,
           amount,
           tokenAddress,
+          tokenDecimals,
+          contributionFee,
         });
         return txn;
       } catch (error) {
@@ -479,10 +509,14 @@ export function useContributeSecret() {
       crowdfundingAddress,
       amount,
       tokenAddress,
+      tokenDecimals,
+      contributionFee,
     }: {
       crowdfundingAddress: string;
       amount: number; // Raw token units (not display units)
       tokenAddress: string;
+      tokenDecimals: number; // Decimals of the campaign token
+      contributionFee: number; // Raw token units charged on top of the amount
     }): Promise<TransactionResult> => {
       if (!account) throw new Error("Wallet not connected");
 
@@ -497,8 +531,11 @@ export function useContributeSecret() {
           crowdfundingAddress
         );
 
-        // Convert raw token units to wei for approval
-        const weiAmount = tokenUnitsToWei(amount);
+        // Convert raw token units to wei for approval, including the fee
+        const weiAmount = tokenUnitsToWei(
+          amount + contributionFee,
+          tokenDecimals
+        );
 
         if (currentAllowance < weiAmount) {
           const approvalTxn = await crowdfundingContract.approveTokens(
@@ -9,11 +9,23 @@ export function displayAmountToTokenUnits(displayAmount: number): number {
   return Math.round(displayAmount * 1_000_000);
 }
 
+// Contract amounts are counted in units of 10^-6 tokens
+export const TOKEN_UNIT_DECIMALS = 6;
+
 /**
- * Convert raw token units to wei for blockchain transfers
+ * Convert raw token units to the token's base units for blockchain transfers
+ * @param tokenUnits Amount in raw token units (10^-6 tokens)
+ * @param tokenDecimals Decimals of the campaign token, as stored in the contract state
  */
-export function tokenUnitsToWei(tokenUnits: number): bigint {
-  const wei = BigInt(tokenUnits) * BigInt("1000000000000");
+export function tokenUnitsToWei(
+  tokenUnits: number,
+  tokenDecimals: number
+): bigint {
+  if (tokenDecimals < TOKEN_UNIT_DECIMALS) {
+    throw new Error(`Token decimals must be at least ${TOKEN_UNIT_DECIMALS}`);
+  }
+  const exponent = tokenDecimals - TOKEN_UNIT_DECIMALS;
+  const wei = BigInt(tokenUnits) * BigInt(10) ** BigInt(exponent);
   return wei;
 }
 